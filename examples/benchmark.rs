@@ -0,0 +1,21 @@
+// cargo run --release --no-default-features --example benchmark
+use tetris_rewrite::{
+    SnakeLib::autopilot,
+    TetrisLib::bot::{self, Weights},
+};
+
+fn main() {
+    let games = 20;
+
+    println!(
+        "snake autopilot: average length {} over {} games on 10x10",
+        autopilot::benchmark(games, 10, 10, 50_000),
+        games
+    );
+
+    println!(
+        "tetris bot: average {} lines over {} games of 500 pieces",
+        bot::benchmark(games, 500, &Weights::default()),
+        games
+    );
+}
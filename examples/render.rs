@@ -0,0 +1,64 @@
+// how many cell writes the diff renderer saves over redrawing every cell each frame.
+// cargo run --release --no-default-features --example render
+use std::collections::VecDeque;
+
+use tetris_rewrite::{
+    DomLib::vgrid::VirtualGrid,
+    GameLib::{Game, Status},
+    SnakeLib::{
+        autopilot,
+        gameboard::{GameBoard, Walls},
+    },
+    TetrisLib::{
+        bot::{self, Weights},
+        Tetris,
+    },
+};
+
+// runs `frames` frames, or until the game is over, and prints the writes of both renderers.
+fn measure<G: Game>(name: &str, game: &mut G, frames: usize, mut frame: impl FnMut(&mut G)) {
+    let mut shown = VirtualGrid::new();
+    let cells = (game.width() * game.height()) as usize;
+    let (mut drawn, mut diffed) = (0, 0);
+
+    shown.diff(game);
+    for _ in 0..frames {
+        if game.status() != Status::Running {
+            break;
+        }
+        frame(game);
+        drawn += 1;
+        diffed += shown.diff(game).len();
+    }
+
+    let full = drawn * cells;
+    println!(
+        "{}: {} frames, {} writes redrawing every cell, {} diffed ({:.1}% of them, {:.1} per frame)",
+        name,
+        drawn,
+        full,
+        diffed,
+        100.0 * diffed as f64 / full as f64,
+        diffed as f64 / drawn as f64,
+    );
+}
+
+fn main() {
+    // the bot plays one move per frame, falling a row when it has nothing left to do.
+    let mut tetris = Tetris::new(10, 25);
+    let mut moves = VecDeque::new();
+    measure("tetris 10x25", &mut tetris, 20_000, |tetris| {
+        if moves.is_empty() {
+            if let Some(placement) = bot::best_placement(tetris, &Weights::default()) {
+                moves.extend(placement.moves);
+            }
+        }
+        match moves.pop_front() {
+            Some(action) => tetris.apply(action),
+            None => Game::tick(tetris),
+        }
+    });
+
+    let mut snake = GameBoard::with_level(0, Walls::Solid, 1).with_seed(7);
+    measure("snake 30x30", &mut snake, 20_000, autopilot::tick);
+}
@@ -0,0 +1,194 @@
+// play Tetris or Snake in a terminal, with the same engines as the web build.
+//
+//   cargo run --no-default-features --features terminal --bin terminal -- tetris
+//   cargo run --no-default-features --features terminal --bin terminal -- snake [--wrap] [--versus] [--food N]
+//
+// arrows move, `q` / `Esc` quits. Tetris: `b` toggles the bot.
+// Snake: `p` toggles the autopilot, WASD steers player two.
+#![allow(non_snake_case)]
+use std::{
+    env,
+    io::{self, Stdout, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use tetris_rewrite::{
+    GameLib::Game,
+    SnakeLib::{
+        autopilot,
+        gameboard::{Action as SnakeAction, Direction as SnakeDirection, GameBoard, Walls},
+    },
+    TetrisLib::{
+        bot::{self, Weights},
+        tetris::{Action as TetrisAction, Tetris},
+    },
+};
+
+const TETRIS_TICK: Duration = Duration::from_millis(1000);
+// one board frame, see `GameBoard::tick`.
+const SNAKE_FRAME: Duration = Duration::from_millis(50);
+
+// raw mode and the alternate screen for as long as it lives.
+struct Screen {
+    out: Stdout,
+}
+
+impl Screen {
+    fn new() -> io::Result<Self> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self { out })
+    }
+
+    fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        queue!(self.out, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+        for line in lines {
+            queue!(self.out, Print(line), Print("\r\n"))?;
+        }
+        self.out.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// wait for a key press until `deadline`.
+fn next_key(deadline: Instant) -> io::Result<Option<KeyCode>> {
+    let timeout = deadline.saturating_duration_since(Instant::now());
+    if event::poll(timeout)? {
+        if let Event::Key(KeyEvent { code, kind: KeyEventKind::Press, .. }) = event::read()? {
+            return Ok(Some(code));
+        }
+    }
+    Ok(None)
+}
+
+// the board framed in box-drawing characters, then `info` and the game-over message.
+// emoji are two columns wide, so empty cells are too.
+fn render<G: Game>(game: &G, info: String) -> Vec<String> {
+    let width = game.width() as usize;
+    let mut lines = vec![format!("┌{}┐", "──".repeat(width))];
+    lines.extend((0..game.height()).map(|y| {
+        let mut line = String::from("│");
+        (0..game.width()).for_each(|x| match game.cell(x, y) {
+            "" => line.push_str("  "),
+            cell => line.push_str(cell),
+        });
+        line.push('│');
+        line
+    }));
+    lines.push(format!("└{}┘", "──".repeat(width)));
+    lines.push(info);
+    if let Some(message) = game.status().message() {
+        lines.push(format!("{} q to quit", message));
+    }
+    lines
+}
+
+fn play_tetris(screen: &mut Screen) -> io::Result<()> {
+    let mut tetris = Tetris::default();
+    let mut bot_on = false;
+    let mut next_tick = Instant::now() + TETRIS_TICK;
+
+    loop {
+        let info = format!("{}{}", tetris.score(), if bot_on { "  [bot]" } else { "" });
+        screen.draw(&render(&tetris, info))?;
+        match next_key(next_tick)? {
+            Some(KeyCode::Char('q')) | Some(KeyCode::Esc) => return Ok(()),
+            Some(KeyCode::Left) => tetris.apply(TetrisAction::Left),
+            Some(KeyCode::Right) => tetris.apply(TetrisAction::Right),
+            Some(KeyCode::Up) => tetris.apply(TetrisAction::Rotate),
+            Some(KeyCode::Down) => tetris.apply(TetrisAction::Down),
+            Some(KeyCode::Char('b')) => bot_on = !bot_on,
+            _ => {}
+        }
+        if Instant::now() >= next_tick {
+            if bot_on {
+                bot::play(&mut tetris, &Weights::default());
+            } else {
+                Game::tick(&mut tetris);
+            }
+            next_tick += TETRIS_TICK;
+        }
+    }
+}
+
+fn play_snake(screen: &mut Screen, walls: Walls, food_count: usize, two_players: bool) -> io::Result<()> {
+    let mut gameboard = GameBoard::with_level(0, walls, food_count);
+    if two_players {
+        gameboard.add_rival();
+    }
+    let mut autopilot_on = false;
+    let mut next_frame = Instant::now() + SNAKE_FRAME;
+
+    loop {
+        let mut info = gameboard.score();
+        if autopilot_on {
+            info.push_str("  [autopilot]");
+        }
+        screen.draw(&render(&gameboard, info))?;
+
+        let action = match next_key(next_frame)? {
+            Some(KeyCode::Char('q')) | Some(KeyCode::Esc) => return Ok(()),
+            Some(KeyCode::Char('p')) => {
+                autopilot_on = !autopilot_on;
+                None
+            }
+            Some(KeyCode::Left) => Some(SnakeAction::Move(SnakeDirection::Left)),
+            Some(KeyCode::Right) => Some(SnakeAction::Move(SnakeDirection::Right)),
+            Some(KeyCode::Up) => Some(SnakeAction::Move(SnakeDirection::Up)),
+            Some(KeyCode::Down) => Some(SnakeAction::Move(SnakeDirection::Down)),
+            Some(KeyCode::Char('w')) => Some(SnakeAction::SteerRival(SnakeDirection::Up)),
+            Some(KeyCode::Char('a')) => Some(SnakeAction::SteerRival(SnakeDirection::Left)),
+            Some(KeyCode::Char('s')) => Some(SnakeAction::SteerRival(SnakeDirection::Down)),
+            Some(KeyCode::Char('d')) => Some(SnakeAction::SteerRival(SnakeDirection::Right)),
+            _ => None,
+        };
+        if let Some(action) = action {
+            gameboard.apply(action);
+        }
+        if Instant::now() >= next_frame {
+            if autopilot_on {
+                autopilot::tick(&mut gameboard);
+            } else {
+                gameboard.tick();
+            }
+            next_frame += SNAKE_FRAME;
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let flag = |name: &str| args.iter().any(|arg| arg == name);
+    let food_count = args
+        .iter()
+        .position(|arg| arg == "--food")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1);
+
+    match args.first().map(String::as_str) {
+        Some("tetris") => play_tetris(&mut Screen::new()?),
+        Some("snake") => {
+            let walls = if flag("--wrap") { Walls::Wrap } else { Walls::Solid };
+            play_snake(&mut Screen::new()?, walls, food_count, flag("--versus"))
+        }
+        _ => {
+            eprintln!("usage: terminal tetris | terminal snake [--wrap] [--versus] [--food N]");
+            Ok(())
+        }
+    }
+}
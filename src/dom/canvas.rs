@@ -0,0 +1,109 @@
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, Document, Element, HtmlCanvasElement, Window};
+
+use crate::{DomLib::vgrid::VirtualGrid, GameLib::Game};
+
+// draws a grid game on a canvas as wide as its container, a colored square or a glyph per cell.
+pub struct CanvasView {
+    window: Window,
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    // css pixels per cell and device pixels per css pixel the canvas was last sized for.
+    scale: (f64, f64),
+    shown: VirtualGrid,
+}
+
+impl CanvasView {
+    pub fn new<G: Game>(window: &Window, document: &Document, game: &G) -> Self {
+        let canvas = document
+            .create_element("canvas")
+            .unwrap()
+            .dyn_into::<HtmlCanvasElement>()
+            .unwrap();
+        canvas
+            .set_attribute(
+                "style",
+                &format!("display: block; width: 100%; aspect-ratio: {} / {}", game.width(), game.height()),
+            )
+            .unwrap();
+        let context = canvas
+            .get_context("2d")
+            .unwrap()
+            .expect("canvas should have a 2d context")
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+
+        Self {
+            window: window.clone(),
+            canvas,
+            context,
+            scale: (0.0, 0.0),
+            shown: VirtualGrid::new(),
+        }
+    }
+
+    pub fn node(&self) -> &Element {
+        &self.canvas
+    }
+
+    // only the cells that changed, unless the canvas had to be resized.
+    pub fn draw<G: Game>(&mut self, game: &G) {
+        let cell_size = self.canvas.client_width() as f64 / game.width() as f64;
+        let scale = (cell_size, self.window.device_pixel_ratio());
+        // not laid out yet.
+        if cell_size <= 0.0 {
+            return;
+        }
+        if scale != self.scale {
+            self.resize(game, scale);
+        }
+
+        let context = &self.context;
+        self.shown.diff(game).into_iter().for_each(|(i, cell)| {
+            let x = (i as u32 % game.width()) as f64 * cell_size;
+            let y = (i as u32 / game.width()) as f64 * cell_size;
+            context.clear_rect(x, y, cell_size, cell_size);
+            match color(cell) {
+                Some(color) => {
+                    context.set_fill_style_str(color);
+                    context.fill_rect(x, y, cell_size, cell_size);
+                }
+                None if cell.is_empty() => {}
+                None => {
+                    let _ = context.fill_text(cell, x + cell_size / 2.0, y + cell_size / 2.0);
+                }
+            }
+        });
+    }
+
+    // back the canvas with one pixel per device pixel, so it stays sharp on high-DPI screens.
+    fn resize<G: Game>(&mut self, game: &G, scale: (f64, f64)) {
+        let (cell_size, ratio) = scale;
+        self.scale = scale;
+        self.canvas.set_width((cell_size * ratio * game.width() as f64).round() as u32);
+        self.canvas.set_height((cell_size * ratio * game.height() as f64).round() as u32);
+
+        // resizing resets the context and clears the canvas.
+        let _ = self.context.set_transform(ratio, 0.0, 0.0, ratio, 0.0, 0.0);
+        self.context.set_font(&format!("{}px sans-serif", cell_size * 0.8));
+        self.context.set_text_align("center");
+        self.context.set_text_baseline("middle");
+        self.shown = VirtualGrid::new();
+    }
+}
+
+// the square emoji are drawn as plain colored cells, they look different in every font.
+fn color(cell: &str) -> Option<&'static str> {
+    match cell {
+        "🟥" => Some("#e74c3c"),
+        "🟧" => Some("#e67e22"),
+        "🟨" => Some("#f1c40f"),
+        "🟩" => Some("#2ecc71"),
+        "🟦" => Some("#3498db"),
+        "🟪" => Some("#9b59b6"),
+        "🟫" => Some("#8d5524"),
+        "⬛" => Some("#2c3e50"),
+        "🧱" => Some("#a0522d"),
+        _ => None,
+    }
+}
@@ -1,6 +1,7 @@
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{Document, Element, Window, HtmlElement};
 
-
+#[derive(Clone)]
 pub struct DomElements {
     pub window: Window,
     pub document: Document,
@@ -13,6 +14,22 @@ pub fn create_div(document: &Document, text: &str) -> Element {
     val
 }
 
+// `target` is an element, a selector, or nothing for the body.
+pub fn find_target(domElements: &DomElements, target: JsValue) -> Result<Element, JsValue> {
+    if target.is_undefined() || target.is_null() {
+        return Ok(domElements.body.clone().into());
+    }
+    if let Some(selector) = target.as_string() {
+        return domElements
+            .document
+            .query_selector(&selector)?
+            .ok_or_else(|| JsValue::from_str(&format!("no element matches {:?}", selector)));
+    }
+    target
+        .dyn_into::<Element>()
+        .map_err(|_| JsValue::from_str("target should be an element or a selector"))
+}
+
 
 
 pub fn init_dom() -> DomElements {
@@ -0,0 +1,458 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use wasm_bindgen::{
+    prelude::{wasm_bindgen, Closure},
+    JsCast,
+};
+use web_sys::{console, Document, Element, HtmlElement, KeyboardEvent, Performance, Window};
+
+use crate::{
+    DomLib::{
+        canvas::CanvasView,
+        domtools::{create_div, DomElements},
+        timestep::{Clock, FixedStep},
+        vgrid::VirtualGrid,
+    },
+    GameLib::{reducer, Event, Game, Status},
+    StateLib::{
+        persist::{default_storage, load, save, Persist},
+        reducer::{use_reducer, Store},
+        state::{use_state, State},
+    },
+};
+
+// js_sys::eval("document.onkeydown = console.log");
+
+// a mounted grid, dropping it stops its loop, removes its key listener and its container.
+pub struct Mount {
+    container: Element,
+    _game_loop: GameLoop,
+    _autosave: Option<Autosave>,
+    to_json: Option<Box<dyn Fn() -> String>>,
+    // kept alive for as long as the listener can call it.
+    keydown: Closure<dyn FnMut(KeyboardEvent)>,
+}
+
+impl Drop for Mount {
+    fn drop(&mut self) {
+        let _ = self
+            .container
+            .remove_event_listener_with_callback("keydown", self.keydown.as_ref().unchecked_ref());
+        self.container.remove();
+    }
+}
+
+impl Mount {
+    // the game as it is now, `None` if it has no `GridOptions::to_json`.
+    pub fn to_json(&self) -> Option<String> {
+        self.to_json.as_ref().map(|to_json| to_json())
+    }
+}
+
+// a `requestAnimationFrame` loop, stopped when dropped.
+struct GameLoop {
+    window: Window,
+    document: Document,
+    frame_id: State<i32>,
+    // requests the next frame from inside itself, `None` once stopped.
+    frame: State<Option<Closure<dyn FnMut()>>>,
+    on_visibility_change: Closure<dyn FnMut()>,
+}
+
+impl Drop for GameLoop {
+    fn drop(&mut self) {
+        let _ = self.window.cancel_animation_frame(*self.frame_id.value());
+        let _ = self.document.remove_event_listener_with_callback(
+            "visibilitychange",
+            self.on_visibility_change.as_ref().unchecked_ref(),
+        );
+        // the frame closure holds `frame` itself, dropping it breaks the cycle.
+        self.frame.set(|_| None);
+    }
+}
+
+// saves the game when the tab is hidden or closed, and once more when dropped.
+struct Autosave {
+    window: Window,
+    document: Document,
+    save: Rc<dyn Fn()>,
+    on_hide: Closure<dyn FnMut()>,
+    on_unload: Closure<dyn FnMut()>,
+    _key: SaveKey,
+}
+
+impl Drop for Autosave {
+    fn drop(&mut self) {
+        let _ = self
+            .window
+            .remove_event_listener_with_callback("beforeunload", self.on_unload.as_ref().unchecked_ref());
+        let _ = self
+            .document
+            .remove_event_listener_with_callback("visibilitychange", self.on_hide.as_ref().unchecked_ref());
+        (self.save)();
+    }
+}
+
+thread_local! {
+    // the save keys of the mounted games.
+    static SAVE_KEYS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+// a save key only one mounted game writes to, released when dropped.
+pub struct SaveKey(String);
+
+impl SaveKey {
+    // `None` while another mounted game saves to `key`.
+    pub fn claim(key: &str) -> Option<Self> {
+        let claimed = SAVE_KEYS
+            .with(|keys| keys.borrow_mut().insert(key.to_string()))
+            .then(|| SaveKey(key.to_string()));
+        if claimed.is_none() {
+            console::warn_1(&format!("another game saves to \"{}\", set a `save_key` to save this one", key).into());
+        }
+        claimed
+    }
+
+    pub fn load<G: Persist>(&self) -> Option<G> {
+        load(&*default_storage(), &self.0)
+    }
+}
+
+impl Drop for SaveKey {
+    fn drop(&mut self) {
+        SAVE_KEYS.with(|keys| keys.borrow_mut().remove(&self.0));
+    }
+}
+
+// `game` saved per target, e.g. `tetris-left` for `<div id="left">`, plain `game` without an id.
+pub fn default_save_key(game: &str, target: &Element) -> String {
+    match target.id().as_str() {
+        "" => game.to_string(),
+        id => format!("{}-{}", game, id),
+    }
+}
+
+struct PerformanceClock(Performance);
+
+impl Clock for PerformanceClock {
+    fn now(&self) -> f64 {
+        self.0.now()
+    }
+}
+
+// how the cells are drawn, picked at init.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Renderer {
+    // a div per cell with the emoji as text.
+    #[default]
+    Dom,
+    // one canvas scaled to the container.
+    Canvas,
+}
+
+// the cell divs, row by row, and what they show.
+struct Blocks {
+    nodes: Vec<Element>,
+    shown: VirtualGrid,
+}
+
+enum View {
+    Dom(Blocks),
+    Canvas(CanvasView),
+}
+
+impl View {
+    fn draw<G: Game>(&mut self, game: &G) {
+        match self {
+            View::Dom(blocks) => blocks.shown.diff(game).into_iter().for_each(|(i, cell)| {
+                blocks.nodes[i].set_inner_html(cell);
+            }),
+            View::Canvas(canvas) => canvas.draw(game),
+        }
+    }
+}
+
+pub struct GridOptions<G: Game> {
+    pub class_name: String,
+    pub renderer: Renderer,
+    // where the game is saved to resume it later, `None` to not save it.
+    pub save_key: Option<SaveKey>,
+    // how `Mount::to_json` writes the game, e.g. with serde.
+    pub to_json: Option<fn(&G) -> String>,
+    // how often `Game::tick` runs, the grid is redrawn whenever a dispatch changes the game.
+    pub step_ms: f64,
+    pub keymap: fn(&str) -> Option<G::Action>,
+    pub autoplay: Option<Autoplay<G>>,
+}
+
+// pressing `key` toggles `play` in place of `Game::tick` on every frame, e.g. a bot.
+pub struct Autoplay<G> {
+    pub key: &'static str,
+    pub play: fn(&mut G),
+}
+
+// mount `game` as a grid at the end of `target`, with its keys and game loop wired up.
+// keys only reach the game while its container has focus, so several games can share a page.
+// the game runs until the returned `Mount` is dropped.
+#[must_use = "dropping the mount unmounts the game"]
+pub fn run_grid<G: Game + Persist + Clone + 'static>(
+    domElements: &DomElements,
+    target: &Element,
+    game: G,
+    options: GridOptions<G>,
+) -> Mount {
+    let DomElements { window, document, .. } = domElements;
+
+    // keys and frames only ever change the game through `dispatch`.
+    let game = use_reducer(reducer(options.autoplay.as_ref().map(|autoplay| autoplay.play)), || game);
+    let (width, height) = (game.value().width(), game.value().height());
+
+    let container = create_div(document, "");
+    container.set_class_name(&options.class_name);
+    // focusable, so it can take the keyboard.
+    container.set_attribute("tabindex", "0").unwrap();
+
+    let view = use_state(|| match options.renderer {
+        Renderer::Dom => View::Dom(append_blocks(document, &*game.value())),
+        Renderer::Canvas => View::Canvas(CanvasView::new(window, document, &*game.value())),
+    });
+    match &*view.value() {
+        View::Dom(blocks) => {
+            container
+                .set_attribute(
+                    "style",
+                    &format!("grid-template: repeat({}, 1rem) / repeat({}, 1rem)", height, width),
+                )
+                .unwrap();
+            let _ = container.append_with_node(&blocks.nodes.iter().cloned().collect());
+        }
+        View::Canvas(canvas) => {
+            // as big as the div grid, smaller if the target is narrower.
+            container
+                .set_attribute("style", &format!("width: {}rem; max-width: 100%", width))
+                .unwrap();
+            container.append_with_node_1(canvas.node()).unwrap();
+        }
+    }
+
+    // shown once the game is over.
+    let overlay = create_div(document, "");
+    overlay.set_class_name("game-overlay");
+    container.append_with_node_1(&overlay).unwrap();
+
+    // under the board, only redrawn when the score changes rather than on every move.
+    let score_panel = create_div(document, &game.value().score());
+    score_panel.set_class_name("game-score");
+    container.append_with_node_1(&score_panel).unwrap();
+    let score = game.memo(|game| game.score());
+    let _ = score.subscribe(move |score| score_panel.set_text_content(Some(score)));
+
+    target.append_with_node_1(&container).unwrap();
+    // after mounting, the canvas needs to know its size.
+    draw_board(&view, &*game.value());
+    draw_overlay(&overlay, &*game.value());
+    // both redraw whenever the game changes.
+    let _ = game.subscribe(move |game| draw_board(&view, game));
+    let _ = game.subscribe(move |game| draw_overlay(&overlay, game));
+    if let Some(container) = container.dyn_ref::<HtmlElement>() {
+        let _ = container.focus();
+    }
+
+    let autoplay = use_state(|| false);
+
+    let keydown = add_keydown_listener(&container, &game, &autoplay, &options);
+    let game_loop = game_loop(domElements, &game, &autoplay, &options);
+
+    let autosave = options.save_key.map(|key| autosave(domElements, &game, key));
+
+    let to_json = options.to_json.map(|to_json| {
+        let game = game.clone();
+        Box::new(move || to_json(&game.value())) as Box<dyn Fn() -> String>
+    });
+
+    Mount {
+        container,
+        _game_loop: game_loop,
+        _autosave: autosave,
+        to_json,
+        keydown,
+    }
+}
+
+// animation frames only advance the fixed step and dispatch its ticks, the board is drawn by
+// the game's subscribers on dispatch. Time spent in a hidden tab doesn't count.
+fn game_loop<G: Game + 'static>(
+    domElements: &DomElements,
+    game: &Store<G, Event<G::Action>>,
+    autoplay: &State<bool>,
+    options: &GridOptions<G>,
+) -> GameLoop {
+    let DomElements { window, document, .. } = domElements;
+    let clock = PerformanceClock(window.performance().expect("window should have performance"));
+    let step = use_state(|| FixedStep::new(clock, options.step_ms));
+
+    let mut frame_id = use_state(|| 0);
+    let mut frame: State<Option<Closure<dyn FnMut()>>> = use_state(|| None);
+
+    let on_frame = {
+        let window = window.clone();
+        let mut game = game.clone();
+        let autoplay = autoplay.clone();
+        let mut step = step.clone();
+        let mut frame_id = frame_id.clone();
+        let frame = frame.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            let mut steps = 0;
+            step.set(|mut step| {
+                steps = step.due();
+                step
+            });
+            let autoplay_on = *autoplay.value();
+            (0..steps).for_each(|_| game.dispatch(if autoplay_on { Event::Autoplay } else { Event::Tick }));
+
+            if let Some(next) = frame.value().as_ref() {
+                let id = window.request_animation_frame(next.as_ref().unchecked_ref()).unwrap();
+                frame_id.set(|_| id);
+            }
+        })
+    };
+    let id = window.request_animation_frame(on_frame.as_ref().unchecked_ref()).unwrap();
+    frame_id.set(|_| id);
+    frame.set(|_| Some(on_frame));
+
+    let on_visibility_change = {
+        let document = document.clone();
+        let mut step = step.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            if document.hidden() {
+                step.set(|mut step| {
+                    step.pause();
+                    step
+                });
+            }
+        })
+    };
+    document
+        .add_event_listener_with_callback("visibilitychange", on_visibility_change.as_ref().unchecked_ref())
+        .unwrap();
+
+    GameLoop {
+        window: window.clone(),
+        document: document.clone(),
+        frame_id,
+        frame,
+        on_visibility_change,
+    }
+}
+
+fn autosave<G: Game + Persist + 'static>(
+    domElements: &DomElements,
+    game: &Store<G, Event<G::Action>>,
+    key: SaveKey,
+) -> Autosave {
+    let DomElements { window, document, .. } = domElements;
+    let storage = default_storage();
+
+    // a finished game isn't worth resuming.
+    let save: Rc<dyn Fn()> = {
+        let game = game.clone();
+        let key = key.0.clone();
+        Rc::new(move || match game.value().status() {
+            Status::Running => save(&*storage, &key, &*game.value()),
+            _ => storage.remove(&key),
+        })
+    };
+    let on_hide = {
+        let document = document.clone();
+        let save = save.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            if document.hidden() {
+                save();
+            }
+        })
+    };
+    let on_unload = {
+        let save = save.clone();
+        Closure::<dyn FnMut()>::new(move || save())
+    };
+    window
+        .add_event_listener_with_callback("beforeunload", on_unload.as_ref().unchecked_ref())
+        .unwrap();
+    document
+        .add_event_listener_with_callback("visibilitychange", on_hide.as_ref().unchecked_ref())
+        .unwrap();
+
+    Autosave {
+        window: window.clone(),
+        document: document.clone(),
+        save,
+        on_hide,
+        on_unload,
+        _key: key,
+    }
+}
+
+fn add_keydown_listener<G: Game + 'static>(
+    container: &Element,
+    game: &Store<G, Event<G::Action>>,
+    autoplay: &State<bool>,
+    options: &GridOptions<G>,
+) -> Closure<dyn FnMut(KeyboardEvent)> {
+    let mut game = game.clone();
+    let mut autoplay = autoplay.clone();
+    let keymap = options.keymap;
+    let autoplay_key = options.autoplay.as_ref().map(|autoplay| autoplay.key);
+
+    let dyn_handle_keydown = Closure::<dyn FnMut(KeyboardEvent)>::new(move |e: KeyboardEvent| {
+        let code = e.code();
+        if autoplay_key == Some(code.as_str()) {
+            autoplay.set(|on| !on);
+        } else if let Some(action) = keymap(&code) {
+            // don't let the arrows scroll the page.
+            e.prevent_default();
+            game.dispatch(Event::Input(action));
+        } else {
+            console::log_1(&code.into());
+        }
+    });
+
+    container
+        .add_event_listener_with_callback("keydown", dyn_handle_keydown.as_ref().unchecked_ref())
+        .unwrap();
+
+    dyn_handle_keydown
+}
+
+// write only the cells that changed since the last draw.
+fn draw_board<G: Game>(view: &State<View>, game: &G) {
+    view.clone().set(|mut view| {
+        view.draw(game);
+        view
+    });
+}
+
+fn draw_overlay<G: Game>(overlay: &Element, game: &G) {
+    let status = game.status();
+    let message = status.message();
+    if overlay.text_content().as_deref() != Some(message.unwrap_or("")) {
+        overlay.set_text_content(message);
+    }
+}
+
+// empty until the first `draw_board`.
+fn append_blocks<G: Game>(document: &Document, game: &G) -> Blocks {
+    let nodes = (0..game.height())
+        .flat_map(|y| (0..game.width()).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let node = create_div(document, "");
+            node.set_attribute("key", &format!("{}-{}", x, y)).unwrap();
+            node
+        })
+        .collect();
+
+    Blocks {
+        nodes,
+        shown: VirtualGrid::new(),
+    }
+}
@@ -0,0 +1,95 @@
+use std::rc::Rc;
+
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::Element;
+
+use crate::{
+    DomLib::{domtools::{create_div, DomElements}, grid::Mount},
+    StateLib::{
+        persist::use_persisted_state,
+        state::{use_state, State},
+    },
+};
+
+// where the key of the last game played is kept.
+const LAST_GAME: &str = "last-game";
+
+pub struct GameEntry {
+    pub key: &'static str,
+    pub name: &'static str,
+    // mounts the game into the given element.
+    pub mount: fn(&DomElements, &Element) -> Mount,
+}
+
+// a menu with a button per game, only the selected game is mounted at a time.
+// starts with the game played last, or the first one.
+pub fn run_launcher(domElements: &DomElements, games: Vec<GameEntry>) {
+    let DomElements { document, body, .. } = domElements;
+    let last_game = use_persisted_state(LAST_GAME, String::new);
+
+    // where the selected game goes, left of the menu.
+    let stage = create_div(document, "");
+    stage.set_class_name("launcher-stage");
+    body.append_with_node_1(&stage).unwrap();
+
+    let menu = document.create_element("ul").unwrap();
+    menu.set_class_name("launcher");
+
+    let buttons: Vec<Element> = games
+        .iter()
+        .map(|game| {
+            let button = document.create_element("button").unwrap();
+            button.set_class_name("button");
+            button.set_attribute("data-key", game.key).unwrap();
+            button.set_text_content(Some(game.name));
+
+            let item = document.create_element("li").unwrap();
+            item.append_with_node_1(&button).unwrap();
+            menu.append_with_node_1(&item).unwrap();
+            button
+        })
+        .collect();
+
+    body.append_with_node_1(&menu).unwrap();
+
+    let current: State<Option<Mount>> = use_state(|| None);
+    let games = Rc::new(games);
+
+    let mut select = {
+        let domElements = domElements.clone();
+        let buttons = buttons.clone();
+        let games = games.clone();
+        let mut current = current.clone();
+        let mut last_game = last_game.clone();
+        move |index: usize| {
+            let game = &games[index];
+            // unmount the previous game before the next one takes over the keyboard.
+            current.set(|mounted| {
+                drop(mounted);
+                Some((game.mount)(&domElements, &stage))
+            });
+            buttons.iter().enumerate().for_each(|(i, button)| {
+                button.set_class_name(if i == index { "active button" } else { "button" });
+            });
+            last_game.set(|_| game.key.to_string());
+        }
+    };
+
+    buttons.iter().enumerate().for_each(|(index, button)| {
+        let mut select = select.clone();
+        let on_click = Closure::<dyn FnMut()>::new(move || select(index));
+        button
+            .add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())
+            .unwrap();
+        // the menu lives as long as the page.
+        on_click.forget();
+    });
+
+    let last = games
+        .iter()
+        .position(|game| *game.key == *last_game.value())
+        .unwrap_or(0);
+    if !games.is_empty() {
+        select(last);
+    }
+}
@@ -1 +1,10 @@
-pub mod domtools;
\ No newline at end of file
+#[cfg(feature = "web")]
+pub mod canvas;
+#[cfg(feature = "web")]
+pub mod domtools;
+#[cfg(feature = "web")]
+pub mod grid;
+#[cfg(feature = "web")]
+pub mod launcher;
+pub mod timestep;
+pub mod vgrid;
@@ -0,0 +1,123 @@
+use std::{cell::Cell, rc::Rc};
+
+// milliseconds from some fixed point, like `performance.now()`.
+pub trait Clock {
+    fn now(&self) -> f64;
+}
+
+// a clock that only moves when told to, clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct FakeClock {
+    now: Rc<Cell<f64>>,
+}
+
+impl FakeClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, ms: f64) {
+        self.now.set(self.now.get() + ms);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> f64 {
+        self.now.get()
+    }
+}
+
+// after a stall, catch up by at most this many steps rather than freezing the page.
+const MAX_STEPS: u32 = 10;
+
+// runs game logic every `step_ms` however often frames come, carrying the remainder over.
+#[derive(Debug)]
+pub struct FixedStep<C: Clock> {
+    clock: C,
+    step_ms: f64,
+    accumulated: f64,
+    last: Option<f64>,
+}
+
+impl<C: Clock> FixedStep<C> {
+    pub fn new(clock: C, step_ms: f64) -> Self {
+        Self {
+            clock,
+            step_ms,
+            accumulated: 0.0,
+            last: None,
+        }
+    }
+
+    // how many steps to run this frame. The first frame, and the first after `pause`, only starts the clock.
+    pub fn due(&mut self) -> u32 {
+        let now = self.clock.now();
+        let elapsed = self.last.map_or(0.0, |last| now - last);
+        self.last = Some(now);
+
+        self.accumulated += elapsed;
+        let steps = (self.accumulated / self.step_ms) as u32;
+        self.accumulated -= steps as f64 * self.step_ms;
+        if steps > MAX_STEPS {
+            self.accumulated = 0.0;
+            return MAX_STEPS;
+        }
+        steps
+    }
+
+    // time until the next `due` doesn't count, e.g. while the tab is hidden.
+    pub fn pause(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FakeClock, FixedStep, MAX_STEPS};
+
+    #[test]
+    fn testDue() {
+        let clock = FakeClock::new();
+        let mut step = FixedStep::new(clock.clone(), 50.0);
+        assert_eq!(step.due(), 0);
+
+        // 100 fps, a step every fifth frame.
+        let steps = (0..100)
+            .map(|_| {
+                clock.advance(10.0);
+                step.due()
+            })
+            .collect::<Vec<u32>>();
+        assert_eq!(steps.iter().sum::<u32>(), 20);
+        assert!(steps.iter().all(|&n| n <= 1));
+
+        clock.advance(120.0);
+        assert_eq!(step.due(), 2);
+        clock.advance(30.0);
+        assert_eq!(step.due(), 1);
+    }
+
+    #[test]
+    fn testPause() {
+        let clock = FakeClock::new();
+        let mut step = FixedStep::new(clock.clone(), 50.0);
+        step.due();
+
+        step.pause();
+        clock.advance(60_000.0);
+        assert_eq!(step.due(), 0);
+        clock.advance(50.0);
+        assert_eq!(step.due(), 1);
+    }
+
+    #[test]
+    fn testCatchUp() {
+        let clock = FakeClock::new();
+        let mut step = FixedStep::new(clock.clone(), 50.0);
+        step.due();
+
+        clock.advance(10_000.0);
+        assert_eq!(step.due(), MAX_STEPS);
+        assert_eq!(step.due(), 0);
+    }
+}
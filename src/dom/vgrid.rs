@@ -0,0 +1,62 @@
+use crate::GameLib::Game;
+
+// the cells of the last frame drawn, keyed by their row-major index,
+// so a frame only writes the cells that changed.
+#[derive(Debug, Default)]
+pub struct VirtualGrid {
+    cells: Vec<String>,
+}
+
+impl VirtualGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the cells that differ from the last frame, with their new content.
+    // the first frame, or one of a different size, changes every cell.
+    pub fn diff<'g, G: Game>(&mut self, game: &'g G) -> Vec<(usize, &'g str)> {
+        let cells = game.cells();
+        if cells.len() != self.cells.len() {
+            self.cells = cells.iter().map(|cell| cell.to_string()).collect();
+            return cells.into_iter().enumerate().collect();
+        }
+
+        cells
+            .into_iter()
+            .zip(self.cells.iter_mut())
+            .enumerate()
+            .filter(|(_, (cell, old))| old.as_str() != *cell)
+            .map(|(i, (cell, old))| {
+                cell.clone_into(old);
+                (i, cell)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::VirtualGrid;
+    use crate::{
+        GameLib::Game,
+        SnakeLib::gameboard::{Direction, GameBoard, Walls},
+    };
+
+    #[test]
+    fn testDiff() {
+        let mut board = GameBoard::new(10, 10, Walls::Solid, 1).with_seed(1);
+        let mut grid = VirtualGrid::new();
+
+        assert_eq!(grid.diff(&board).len(), 100);
+        assert!(grid.diff(&board).is_empty());
+
+        board.try_move(Direction::Up);
+        let changed = grid.diff(&board);
+        // the head moved and the tail followed.
+        assert!(!changed.is_empty() && changed.len() <= 4);
+        changed.iter().for_each(|(i, cell)| {
+            assert_eq!(board.cell(*i as u32 % 10, *i as u32 / 10), *cell);
+        });
+        assert!(grid.diff(&board).is_empty());
+    }
+}
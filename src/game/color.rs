@@ -0,0 +1,79 @@
+use crate::StateLib::persist::Persist;
+
+// the colors pieces are drawn in, owned so boards can be saved and loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Brown,
+    Black,
+}
+
+impl Color {
+    pub const ALL: [Color; 8] = [
+        Color::Red,
+        Color::Orange,
+        Color::Yellow,
+        Color::Green,
+        Color::Blue,
+        Color::Purple,
+        Color::Brown,
+        Color::Black,
+    ];
+
+    // a block of this color, e.g. a tetris cell or a snake's body.
+    pub fn square(&self) -> &'static str {
+        match self {
+            Color::Red => "🟥",
+            Color::Orange => "🟧",
+            Color::Yellow => "🟨",
+            Color::Green => "🟩",
+            Color::Blue => "🟦",
+            Color::Purple => "🟪",
+            Color::Brown => "🟫",
+            Color::Black => "⬛",
+        }
+    }
+
+    // a round one, e.g. a snake's head.
+    pub fn circle(&self) -> &'static str {
+        match self {
+            Color::Red => "🔴",
+            Color::Orange => "🟠",
+            Color::Yellow => "🟡",
+            Color::Green => "🟢",
+            Color::Blue => "🔵",
+            Color::Purple => "🟣",
+            Color::Brown => "🟤",
+            Color::Black => "⚫",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Color::Red => "red",
+            Color::Orange => "orange",
+            Color::Yellow => "yellow",
+            Color::Green => "green",
+            Color::Blue => "blue",
+            Color::Purple => "purple",
+            Color::Brown => "brown",
+            Color::Black => "black",
+        }
+    }
+}
+
+impl Persist for Color {
+    fn encode(&self) -> String {
+        self.name().to_string()
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        Color::ALL.into_iter().find(|color| color.name() == text)
+    }
+}
@@ -0,0 +1,97 @@
+pub mod color;
+
+// what a grid game shows and accepts, so front ends don't need to know which game they run.
+pub trait Game {
+    type Action;
+
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    // the emoji drawn in a cell, empty for a blank one.
+    fn cell(&self, x: u32, y: u32) -> &str;
+    // every cell, row by row. Override when one pass is cheaper than asking cell by cell.
+    fn cells(&self) -> Vec<&str> {
+        (0..self.height())
+            .flat_map(|y| (0..self.width()).map(move |x| self.cell(x, y)))
+            .collect()
+    }
+    fn apply(&mut self, action: Self::Action);
+    // advance one frame of the front end's timer.
+    fn tick(&mut self);
+    fn status(&self) -> Status;
+    // a line about the score, for a panel next to the board.
+    fn score(&self) -> String;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    Won(String),
+    Lost(String),
+}
+
+impl Status {
+    // what to tell the player once the game is over.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Status::Running => None,
+            Status::Won(message) | Status::Lost(message) => Some(message),
+        }
+    }
+}
+
+// everything that happens to a game, see `reducer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<A> {
+    Input(A),
+    Tick,
+    // a frame played by `autoplay` in place of `Tick`.
+    Autoplay,
+}
+
+// a reducer for `StateLib::reducer::use_reducer`, `autoplay` plays a frame for the player, e.g. a bot.
+pub fn reducer<G: Game>(autoplay: Option<fn(&mut G)>) -> impl Fn(G, Event<G::Action>) -> G {
+    move |mut game, event| {
+        match (event, autoplay) {
+            (Event::Input(action), _) => game.apply(action),
+            (Event::Autoplay, Some(play)) => play(&mut game),
+            (Event::Tick, _) | (Event::Autoplay, None) => game.tick(),
+        }
+        game
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{reducer, Event, Game};
+    use crate::{
+        SnakeLib::{
+            autopilot,
+            gameboard::{Action, Direction, GameBoard, Walls},
+        },
+        StateLib::reducer::{replay, use_reducer},
+    };
+
+    fn board() -> GameBoard {
+        GameBoard::new(12, 12, Walls::Solid, 2).with_seed(3)
+    }
+
+    #[test]
+    fn testReplay() {
+        let mut store = use_reducer(reducer(Some(autopilot::tick)), board);
+        let log = Rc::new(RefCell::new(vec![]));
+        {
+            let log = log.clone();
+            store.on_dispatch(move |event| log.borrow_mut().push(*event));
+        }
+
+        store.dispatch(Event::Input(Action::Move(Direction::Up)));
+        (0..200).for_each(|_| store.dispatch(Event::Tick));
+        (0..500).for_each(|_| store.dispatch(Event::Autoplay));
+
+        let replayed = replay(reducer(Some(autopilot::tick)), board(), log.borrow().iter().copied());
+        assert_eq!(replayed.cells(), store.value().cells());
+        assert_eq!(replayed.snake.score, store.value().snake.score);
+    }
+}
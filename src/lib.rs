@@ -1,35 +1,134 @@
 #![allow(non_snake_case)]
 #[path = "./tetris/lib.rs"]
-mod TetrisLib;
+pub mod TetrisLib;
+
+#[path = "./game/lib.rs"]
+pub mod GameLib;
 
 #[path = "./state/lib.rs"]
-mod StateLib;
+pub mod StateLib;
 
 #[path = "./snake/lib.rs"]
-mod SnakeLib;
+pub mod SnakeLib;
 
 #[path = "./dom/lib.rs"]
-mod DomLib;
+pub mod DomLib;
+
+
+#[cfg(feature = "web")]
+use DomLib::{
+    domtools::{find_target, init_dom, DomElements},
+    grid::Mount,
+    launcher::{run_launcher, GameEntry},
+};
+#[cfg(feature = "web")]
+use SnakeLib::{run_snake, SnakeOptions};
+#[cfg(feature = "web")]
+use TetrisLib::{run_tetris, TetrisOptions};
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::{wasm_bindgen, JsValue};
+#[cfg(feature = "web")]
+use web_sys::Element;
 
 
-use DomLib::domtools::init_dom;
-use SnakeLib::run_snake;
-use TetrisLib::run_tetris;
-use wasm_bindgen::prelude::wasm_bindgen;
 
 
 
+// a running game, returned to the host page by the init functions.
+#[cfg(feature = "web")]
+#[wasm_bindgen]
+pub struct GameHandle {
+    mount: Option<Mount>,
+}
+
+#[cfg(feature = "web")]
+#[wasm_bindgen]
+impl GameHandle {
+    // stops the game: clears its timer, removes its key listener and its container.
+    // calling it again does nothing, so does `free()` afterwards.
+    pub fn destroy(&mut self) {
+        self.mount.take();
+    }
+
+    // the game's state as JSON for `JSON.parse`, `undefined` once destroyed
+    // or when built without the `serde` feature.
+    pub fn to_json(&self) -> Option<String> {
+        self.mount.as_ref()?.to_json()
+    }
+}
 
+#[cfg(feature = "web")]
+impl From<Mount> for GameHandle {
+    fn from(mount: Mount) -> Self {
+        Self { mount: Some(mount) }
+    }
+}
 
+// `target` is an element or a selector to mount the game into, the body by default.
+#[cfg(feature = "web")]
 #[wasm_bindgen]
-pub fn init_tetris() {
+pub fn init_tetris(target: JsValue, options: Option<TetrisOptions>) -> Result<GameHandle, JsValue> {
     let domElements = init_dom();
+    let target = find_target(&domElements, target)?;
 
-    let _ = run_tetris(&domElements);
+    Ok(run_tetris(&domElements, &target, &options.unwrap_or_default()).into())
 }
 
+// `target` as for `init_tetris`.
+#[cfg(feature = "web")]
 #[wasm_bindgen]
-pub fn init_snake() {
+pub fn init_snake(target: JsValue, options: Option<SnakeOptions>) -> Result<GameHandle, JsValue> {
     let domElements = init_dom();
-    run_snake(&domElements);
-}
\ No newline at end of file
+    let target = find_target(&domElements, target)?;
+
+    Ok(run_snake(&domElements, &target, &options.unwrap_or_default()).into())
+}
+
+#[cfg(feature = "web")]
+fn mount_tetris(domElements: &DomElements, target: &Element) -> Mount {
+    run_tetris(domElements, target, &TetrisOptions::default())
+}
+
+#[cfg(feature = "web")]
+fn mount_snake(domElements: &DomElements, target: &Element) -> Mount {
+    run_snake(domElements, target, &SnakeOptions::default())
+}
+
+// saved apart from the other snake, a resumed game keeps the walls it was saved with.
+#[cfg(feature = "web")]
+fn mount_snake_wrap(domElements: &DomElements, target: &Element) -> Mount {
+    let options = SnakeOptions {
+        wrap_walls: true,
+        save_key: Some("snake-wrap".to_string()),
+        ..SnakeOptions::default()
+    };
+    run_snake(domElements, target, &options)
+}
+
+// a menu to switch between the games, one mounted at a time.
+#[cfg(feature = "web")]
+#[wasm_bindgen]
+pub fn init_launcher() {
+    let domElements = init_dom();
+
+    run_launcher(
+        &domElements,
+        vec![
+            GameEntry {
+                key: "tetris",
+                name: "Tetris",
+                mount: mount_tetris,
+            },
+            GameEntry {
+                key: "snake",
+                name: "Snake",
+                mount: mount_snake,
+            },
+            GameEntry {
+                key: "snake-wrap",
+                name: "Snake, wrap-around walls",
+                mount: mount_snake_wrap,
+            },
+        ],
+    );
+}
@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{
+    food::FoodKind,
+    gameboard::{Direction, GameBoard, Walls},
+    pos::Pos,
+    snake::Snake,
+};
+
+const DIRECTIONS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+// pick player one's next direction:
+// 1. the shortest path to food, if the tail can still be reached afterwards,
+// 2. otherwise the next cell of a Hamiltonian cycle, if the board has one and it is safe,
+// 3. otherwise chase the tail,
+// 4. otherwise the move that keeps the most cells reachable.
+pub fn next_direction(board: &GameBoard) -> Direction {
+    let snake = &board.snake;
+    let blocked = blocked_cells(board);
+
+    if let Some(path) = path_to_food(board, &blocked) {
+        if is_safe_path(board, snake, &path, true) {
+            return direction_to(board, snake.positions.front().unwrap(), &path[0]);
+        }
+    }
+
+    if let Some(direction) = follow_cycle(board, &blocked) {
+        return direction;
+    }
+
+    let head = snake.positions.front().unwrap();
+    let tail = snake.positions.back().unwrap();
+    if let Some(path) = bfs(board, head, &blocked, |pos| pos == tail) {
+        return direction_to(board, head, &path[0]);
+    }
+
+    safest_direction(board, &blocked)
+}
+
+// steer before the board steps on its own, call it every frame instead of `GameBoard::tick`.
+pub fn tick(board: &mut GameBoard) {
+    if !board.is_over() && board.frame + 1 >= board.pace {
+        let direction = next_direction(board);
+        board.turn(direction);
+    }
+    board.tick();
+}
+
+// average length reached over `games` seeded games on a plain `width` x `height` board,
+// a game stops after `max_steps` steps even if nobody has lost yet.
+pub fn benchmark(games: u64, width: u32, height: u32, max_steps: usize) -> f64 {
+    let total: usize = (0..games)
+        .map(|seed| {
+            let mut board = GameBoard::new(width, height, Walls::Solid, 1).with_seed(seed);
+            for _ in 0..max_steps {
+                if board.is_over() {
+                    break;
+                }
+                board.try_move(next_direction(&board));
+            }
+            board.snake.positions.len()
+        })
+        .sum();
+    total as f64 / games as f64
+}
+
+// cells the snake must not enter this step: obstacles, poison, the rival,
+// and its own body except the tail, which moves out of the way.
+fn blocked_cells(board: &GameBoard) -> HashSet<Pos> {
+    let snake = &board.snake;
+    let mut blocked = board.obstacles.clone();
+    blocked.extend(snake.positions.iter().take(snake.positions.len() - 1));
+    blocked.extend(board.rival.iter().flat_map(|rival| rival.positions.iter()));
+    blocked.extend(
+        board
+            .foods
+            .iter()
+            .filter(|food| food.kind == FoodKind::Poison)
+            .map(|food| food.pos),
+    );
+    blocked
+}
+
+fn neighbours<'b>(board: &'b GameBoard, pos: &'b Pos) -> impl Iterator<Item = (Direction, Pos)> + 'b {
+    DIRECTIONS.iter().filter_map(move |direction| {
+        let next = board.next_position(pos, direction);
+        let inside = next.0 >= 0 && next.0 < board.width as i32 && next.1 >= 0 && next.1 < board.height as i32;
+        inside.then_some((*direction, next))
+    })
+}
+
+fn direction_to(board: &GameBoard, from: &Pos, to: &Pos) -> Direction {
+    neighbours(board, from)
+        .find(|(_, next)| next == to)
+        .map(|(direction, _)| direction)
+        .expect("cells should be adjacent")
+}
+
+// breadth-first search from `from` to the first cell accepted by `is_goal`,
+// the returned path leaves out `from`.
+fn bfs(board: &GameBoard, from: &Pos, blocked: &HashSet<Pos>, is_goal: impl Fn(&Pos) -> bool) -> Option<Vec<Pos>> {
+    let mut came_from: HashMap<Pos, Pos> = HashMap::new();
+    let mut queue = VecDeque::from([*from]);
+
+    while let Some(pos) = queue.pop_front() {
+        if pos != *from && is_goal(&pos) {
+            let mut path = vec![pos];
+            let mut current = pos;
+            while let Some(prev) = came_from.get(&current) {
+                if prev == from {
+                    break;
+                }
+                path.push(*prev);
+                current = *prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for (_, next) in neighbours(board, &pos) {
+            if next != *from && !blocked.contains(&next) && !came_from.contains_key(&next) {
+                came_from.insert(next, pos);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn path_to_food(board: &GameBoard, blocked: &HashSet<Pos>) -> Option<Vec<Pos>> {
+    let targets: HashSet<Pos> = board
+        .foods
+        .iter()
+        .filter(|food| food.kind.grows())
+        .map(|food| food.pos)
+        .collect();
+    if targets.is_empty() {
+        return None;
+    }
+    bfs(board, board.snake.positions.front().unwrap(), blocked, |pos| targets.contains(pos))
+}
+
+// walk a copy of the snake along `path`, then make sure its head can still reach its tail.
+fn is_safe_path(board: &GameBoard, snake: &Snake, path: &[Pos], grows_at_end: bool) -> bool {
+    let mut positions = snake.positions.clone();
+    for (i, pos) in path.iter().enumerate() {
+        positions.push_front(*pos);
+        if i + 1 < path.len() || !grows_at_end {
+            positions.pop_back();
+        }
+    }
+
+    let tail = *positions.back().unwrap();
+    let mut blocked = board.obstacles.clone();
+    blocked.extend(positions.iter().take(positions.len() - 1));
+    blocked.extend(board.rival.iter().flat_map(|rival| rival.positions.iter()));
+    blocked.remove(&tail);
+
+    bfs(board, positions.front().unwrap(), &blocked, |pos| *pos == tail).is_some()
+}
+
+// a closed walk through every cell of an obstacle-free board, it exists when
+// one side is even: along the top row, zigzag back through the rest, then up the first column.
+pub fn hamiltonian_cycle(width: u32, height: u32) -> Option<Vec<Pos>> {
+    if !height.is_multiple_of(2) {
+        return if width.is_multiple_of(2) && height > 1 {
+            hamiltonian_cycle(height, width).map(|cycle| cycle.iter().map(|pos| Pos(pos.1, pos.0)).collect())
+        } else {
+            None
+        };
+    }
+    if width < 2 {
+        return None;
+    }
+
+    let (width, height) = (width as i32, height as i32);
+    let mut cycle: Vec<Pos> = (0..width).map(|x| Pos(x, 0)).collect();
+    for y in 1..height {
+        if y % 2 == 1 {
+            cycle.extend((1..width).rev().map(|x| Pos(x, y)));
+        } else {
+            cycle.extend((1..width).map(|x| Pos(x, y)));
+        }
+    }
+    cycle.extend((1..height).rev().map(|y| Pos(0, y)));
+    Some(cycle)
+}
+
+fn follow_cycle(board: &GameBoard, blocked: &HashSet<Pos>) -> Option<Direction> {
+    if !board.obstacles.is_empty() {
+        return None;
+    }
+    let cycle = hamiltonian_cycle(board.width, board.height)?;
+    let head = board.snake.positions.front().unwrap();
+    let index = cycle.iter().position(|pos| pos == head)?;
+    let next = cycle[(index + 1) % cycle.len()];
+    if blocked.contains(&next)
+        || !neighbours(board, head).any(|(_, pos)| pos == next)
+        || !is_safe_path(board, &board.snake, &[next], board.foods.iter().any(|food| food.pos == next))
+    {
+        return None;
+    }
+    Some(direction_to(board, head, &next))
+}
+
+// number of cells reachable from `from`.
+fn reachable(board: &GameBoard, from: &Pos, blocked: &HashSet<Pos>) -> usize {
+    let mut seen = HashSet::from([*from]);
+    let mut queue = VecDeque::from([*from]);
+    while let Some(pos) = queue.pop_front() {
+        for (_, next) in neighbours(board, &pos) {
+            if !blocked.contains(&next) && seen.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    seen.len()
+}
+
+fn safest_direction(board: &GameBoard, blocked: &HashSet<Pos>) -> Direction {
+    let head = board.snake.positions.front().unwrap();
+    neighbours(board, head)
+        .filter(|(_, next)| !blocked.contains(next))
+        .max_by_key(|(_, next)| reachable(board, next, blocked))
+        .map(|(direction, _)| direction)
+        .unwrap_or(board.snake.heading)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{benchmark, hamiltonian_cycle, next_direction};
+    use crate::SnakeLib::{
+        food::FoodKind,
+        gameboard::{Direction, GameBoard, Walls},
+        pos::Pos,
+    };
+
+    // a snake heading left from the middle of a 10x10 board.
+    fn snake_board(foods: Vec<(Pos, FoodKind)>) -> GameBoard {
+        GameBoard::fixture(10, 10, Walls::Solid, vec![Pos(5, 5), Pos(6, 5), Pos(7, 5)], foods)
+    }
+
+    #[test]
+    fn testHamiltonianCycle() {
+        for (width, height) in [(4, 4), (5, 4), (4, 5), (2, 2)] {
+            let cycle = hamiltonian_cycle(width, height).unwrap();
+            assert_eq!(cycle.len(), (width * height) as usize);
+            assert_eq!(cycle.iter().collect::<HashSet<&Pos>>().len(), cycle.len());
+            for (i, pos) in cycle.iter().enumerate() {
+                let next = cycle[(i + 1) % cycle.len()];
+                assert_eq!((pos.0 - next.0).abs() + (pos.1 - next.1).abs(), 1);
+            }
+        }
+        assert!(hamiltonian_cycle(5, 5).is_none());
+    }
+
+    #[test]
+    fn testHeadsForFood() {
+        let board = snake_board(vec![(Pos(5, 2), FoodKind::Normal)]);
+
+        assert_eq!(next_direction(&board), Direction::Up);
+    }
+
+    #[test]
+    fn testAvoidsPoison() {
+        let board = snake_board(vec![(Pos(4, 5), FoodKind::Poison), (Pos(3, 5), FoodKind::Normal)]);
+
+        assert_ne!(next_direction(&board), Direction::Left);
+    }
+
+    #[test]
+    fn testBenchmark() {
+        // the snake starts at 3 cells, 15 is close to half the board.
+        assert!(benchmark(2, 6, 6, 2_000) > 15.0);
+    }
+}
@@ -1,8 +1,135 @@
 use super::pos::Pos;
+use crate::GameLib::color::Color;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FoodKind {
+    // grows the snake by one.
+    Normal,
+    // worth more points, but vanishes after a while.
+    Bonus,
+    // cuts a few segments off the tail.
+    Shrink,
+    SpeedUp,
+    SlowDown,
+    // ends the game.
+    Poison,
+}
 
+const SPAWN_CHANCES: [(FoodKind, f64); 6] = [
+    (FoodKind::Normal, 0.70),
+    (FoodKind::Bonus, 0.10),
+    (FoodKind::Shrink, 0.06),
+    (FoodKind::SpeedUp, 0.05),
+    (FoodKind::SlowDown, 0.05),
+    (FoodKind::Poison, 0.04),
+];
 
-pub struct  Food<'a> {
-    pub food_color: &'a str,
-    pub pos: Pos
-}
\ No newline at end of file
+const NORMAL_COLORS: [Color; 7] = [
+    Color::Red,
+    Color::Orange,
+    Color::Yellow,
+    Color::Green,
+    Color::Blue,
+    Color::Purple,
+    Color::Brown,
+];
+
+impl FoodKind {
+    // map a roll in `0.0..1.0` onto a kind according to `SPAWN_CHANCES`.
+    pub fn pick(roll: f64) -> Self {
+        let mut acc = 0.0;
+        for (kind, chance) in SPAWN_CHANCES {
+            acc += chance;
+            if roll < acc {
+                return kind;
+            }
+        }
+        FoodKind::Normal
+    }
+
+    // `roll` only matters for normal food, which comes in several colors.
+    pub fn color(&self, roll: f64) -> Color {
+        NORMAL_COLORS[(roll * NORMAL_COLORS.len() as f64) as usize % NORMAL_COLORS.len()]
+    }
+
+    // the special kinds are drawn as a picture, normal food as a square of its color.
+    pub fn icon(&self) -> Option<&'static str> {
+        match self {
+            FoodKind::Normal => None,
+            FoodKind::Bonus => Some("🍒"),
+            FoodKind::Shrink => Some("🍄"),
+            FoodKind::SpeedUp => Some("⚡"),
+            FoodKind::SlowDown => Some("🐢"),
+            FoodKind::Poison => Some("💀"),
+        }
+    }
+
+    // number of snake steps before the food disappears.
+    pub fn lifetime(&self) -> Option<u32> {
+        match self {
+            FoodKind::Bonus => Some(40),
+            FoodKind::Poison => Some(80),
+            _ => None,
+        }
+    }
+
+    pub fn points(&self) -> u32 {
+        match self {
+            FoodKind::Normal => 1,
+            FoodKind::Bonus => 5,
+            _ => 0,
+        }
+    }
+
+    pub fn grows(&self) -> bool {
+        matches!(self, FoodKind::Normal | FoodKind::Bonus)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Food {
+    pub color: Color,
+    pub pos: Pos,
+    pub kind: FoodKind,
+    // steps left before the food vanishes, `None` if it stays forever.
+    pub ttl: Option<u32>,
+}
+
+impl Food {
+    pub fn new(pos: Pos, kind: FoodKind, color_roll: f64) -> Self {
+        Self {
+            color: kind.color(color_roll),
+            pos,
+            kind,
+            ttl: kind.lifetime(),
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        self.kind.icon().unwrap_or_else(|| self.color.square())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Food, FoodKind};
+    use crate::{GameLib::color::Color, SnakeLib::pos::Pos};
+
+    #[test]
+    fn testPick() {
+        assert_eq!(FoodKind::pick(0.0), FoodKind::Normal);
+        assert_eq!(FoodKind::pick(0.75), FoodKind::Bonus);
+        assert_eq!(FoodKind::pick(0.82), FoodKind::Shrink);
+        assert_eq!(FoodKind::pick(0.99), FoodKind::Poison);
+    }
+
+    #[test]
+    fn testNormalColor() {
+        assert_eq!(FoodKind::Normal.color(0.0), Color::Red);
+        assert_eq!(FoodKind::Normal.color(0.999), Color::Brown);
+        assert_eq!(Food::new(Pos(0, 0), FoodKind::Normal, 0.0).emoji(), "🟥");
+        assert_eq!(Food::new(Pos(0, 0), FoodKind::Bonus, 0.0).emoji(), "🍒");
+    }
+}
@@ -1,9 +1,13 @@
-use std::ops::Mul;
+use std::{collections::HashSet, fmt};
 
-use js_sys::Math::random;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
 
-use super::{food::Food, pos::Pos, snake::Snake};
+use crate::GameLib::{color::Color, Game, Status};
+use super::{food::{Food, FoodKind}, level::levels, pos::Pos, snake::Snake};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Up,
     Down,
@@ -11,71 +15,432 @@ pub enum Direction {
     Right,
 }
 
-pub struct GameBoard<'a> {
+impl Direction {
+    pub fn delta(&self) -> Pos {
+        match self {
+            Direction::Down => Pos(0, 1),
+            Direction::Up => Pos(0, -1),
+            Direction::Left => Pos(-1, 0),
+            Direction::Right => Pos(1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // player one, see `GameBoard::try_move`.
+    Move(Direction),
+    SteerRival(Direction),
+}
+
+// what happens when the snake leaves the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Walls {
+    // every edge is lethal.
+    Solid,
+    // leaving one edge re-enters from the opposite edge.
+    Wrap,
+}
+
+// ticker frames between two steps of the snake.
+const NORMAL_PACE: u32 = 6;
+const FAST_PACE: u32 = 3;
+const SLOW_PACE: u32 = 10;
+// how many steps a speed pickup lasts.
+const PACE_STEPS: u32 = 50;
+// tail segments removed by shrinking food.
+const SHRINK_BY: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LossCause {
+    Wall,
+    Obstacle,
+    SelfCollision,
+    // ran into the other player's snake.
+    Rival,
+    HeadOn,
+    Poison,
+}
+
+impl fmt::Display for LossCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LossCause::Wall => write!(f, "hit the wall"),
+            LossCause::Obstacle => write!(f, "hit an obstacle"),
+            LossCause::SelfCollision => write!(f, "bit itself"),
+            LossCause::Rival => write!(f, "ran into the other snake"),
+            LossCause::HeadOn => write!(f, "head-on collision"),
+            LossCause::Poison => write!(f, "ate poison"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameStatus {
+    Running,
+    // the snake filled every free cell.
+    Won,
+    // in a two-player game this is the crash that ended it, see `GameBoard::winner`.
+    Lost(LossCause),
+}
+
+// result of a two-player game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Winner {
+    PlayerOne,
+    PlayerTwo,
+    Draw,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameBoard {
     pub width: u32,
     pub height: u32,
-    pub snake: Snake<'a>,
-    pub is_failed: bool,
-    pub food: Food<'a>,
+    pub walls: Walls,
+    pub obstacles: HashSet<Pos>,
+    // index into `levels()`, `None` for a plain board.
+    pub level: Option<usize>,
+    pub snake: Snake,
+    // player two, `None` in a single-player game.
+    pub rival: Option<Snake>,
+    pub status: GameStatus,
+    pub foods: Vec<Food>,
+    // how many food items are kept on the board at once.
+    pub food_count: usize,
+    // ticker frames between two steps, speed food changes it for a while.
+    pub pace: u32,
+    // steps until `pace` is back to normal.
+    pub pace_steps_left: u32,
+    // ticker frames since the last step.
+    pub frame: u32,
+    // decides where food spawns and what kind it is.
+    pub rng: ChaCha12Rng,
 }
 
-impl GameBoard<'_> {
-    pub fn new(width: u32, height: u32) -> Self {
-        Self {
+impl GameBoard {
+    pub fn new(width: u32, height: u32, walls: Walls, food_count: usize) -> Self {
+        let mut gameboard = Self {
             width,
             height,
+            walls,
+            obstacles: HashSet::new(),
+            level: None,
             snake: Snake::new(
                 (0..3)
                     .map(|n| Pos(width as i32 / 2 + n, height as i32 / 2))
                     .collect(),
             ),
-            is_failed: false,
-            food: Food {
-                pos: Pos(
-                    random().mul(width as f64).floor() as i32,
-                    random().mul(height as f64).floor() as i32
-                ),
-                food_color: vec!["🟥", "🟧", "🟨", "🟩", "🟦", "🟪", "🟫"].get(random().mul(7.0).floor() as usize).unwrap()
+            rival: None,
+            status: GameStatus::Running,
+            foods: vec![],
+            food_count,
+            pace: NORMAL_PACE,
+            pace_steps_left: 0,
+            frame: 0,
+            rng: ChaCha12Rng::from_entropy(),
+        };
+        gameboard.fill_food();
+        gameboard
+    }
+
+    // replay the same food drops for the same seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+        self.foods.clear();
+        self.fill_food();
+        self
+    }
+
+    pub fn with_level(index: usize, walls: Walls, food_count: usize) -> Self {
+        let level = &levels()[index];
+        let mut gameboard = Self::new(level.width, level.height, walls, food_count);
+        gameboard.load_level(index);
+        gameboard
+    }
+
+    // put player two on the board, mirrored through the centre from player one.
+    pub fn add_rival(&mut self) {
+        let positions = self
+            .snake
+            .positions
+            .iter()
+            .map(|pos| Pos(self.width as i32 - 1 - pos.0, self.height as i32 - 1 - pos.1))
+            .collect::<Vec<Pos>>();
+        self.foods.retain(|food| !positions.contains(&food.pos));
+        self.rival = Some(Snake::with_color(positions, Direction::Right, Color::Blue));
+        self.fill_food();
+    }
+
+    fn load_level(&mut self, index: usize) {
+        let level = &levels()[index];
+        self.width = level.width;
+        self.height = level.height;
+        self.obstacles = level.obstacles.clone();
+        self.level = Some(index);
+        let score = self.snake.score;
+        self.snake = Snake::new(level.start_positions());
+        self.snake.score = score;
+        self.foods.clear();
+        self.fill_food();
+    }
+
+    // move on to the next map once the snake is long enough,
+    // the last map is played endlessly. Two-player games stay on their map.
+    fn check_level_progress(&mut self) {
+        let Some(index) = self.level else {
+            return;
+        };
+        let all_levels = levels();
+        if self.rival.is_none()
+            && self.snake.positions.len() >= all_levels[index].target_length
+            && index + 1 < all_levels.len()
+        {
+            self.load_level(index + 1);
+        }
+    }
+
+    // top the board up to `food_count` items, each on a free cell.
+    fn fill_food(&mut self) {
+        let cells = (self.width * self.height) as usize;
+        let snake_cells = self.snakes().map(|snake| snake.positions.len()).sum::<usize>();
+        while self.foods.len() < self.food_count
+            && self.obstacles.len() + snake_cells + self.foods.len() < cells
+        {
+            let mut pos = self.random_position();
+            while !self.is_free(&pos) {
+                pos = self.random_position();
             }
+            let kind = FoodKind::pick(self.rng.gen());
+            let color_roll = self.rng.gen();
+            self.foods.push(Food::new(pos, kind, color_roll));
         }
     }
 
-    fn refresh_food(&mut self) {
-        self.food = Food {
-            pos: Pos(
-                random().mul(self.width as f64).floor() as i32,
-                random().mul(self.height as f64).floor() as i32
-            ),
-            food_color: vec!["🟥", "🟧", "🟨", "🟩", "🟦", "🟪", "🟫"].get(random().mul(7.0).floor() as usize).unwrap()
+    fn is_free(&self, pos: &Pos) -> bool {
+        !self.obstacles.contains(pos)
+            && !self.snakes().any(|snake| snake.positions.contains(pos))
+            && !self.foods.iter().any(|food| food.pos == *pos)
+    }
+
+    fn random_position(&mut self) -> Pos {
+        Pos(
+            self.rng.gen_range(0..self.width as i32),
+            self.rng.gen_range(0..self.height as i32),
+        )
+    }
+
+    pub fn snakes(&self) -> impl Iterator<Item = &Snake> {
+        std::iter::once(&self.snake).chain(self.rival.as_ref())
+    }
+
+    fn player(&self, player: usize) -> &Snake {
+        match player {
+            0 => &self.snake,
+            _ => self.rival.as_ref().expect("there is no player two"),
+        }
+    }
+
+    fn player_mut(&mut self, player: usize) -> &mut Snake {
+        match player {
+            0 => &mut self.snake,
+            _ => self.rival.as_mut().expect("there is no player two"),
         }
     }
 
+    pub fn is_over(&self) -> bool {
+        self.status != GameStatus::Running
+    }
+
+    pub fn winner(&self) -> Option<Winner> {
+        let rival = self.rival.as_ref()?;
+        match (self.snake.is_dead, rival.is_dead) {
+            (false, false) => None,
+            (false, true) => Some(Winner::PlayerOne),
+            (true, false) => Some(Winner::PlayerTwo),
+            (true, true) => Some(Winner::Draw),
+        }
+    }
+
+    // called by the ticker every frame. the snakes don't wait for a key press: they step along
+    // their heading every `pace` frames, and a key press steps right away and restarts the count.
+    pub fn tick(&mut self) {
+        self.frame += 1;
+        if self.frame >= self.pace {
+            self.step();
+        }
+    }
+
+    // player one steps right away in a single-player game;
+    // with two players the direction is only taken on the next tick.
     pub fn try_move(&mut self, direction: Direction) {
-        if self.is_failed {
+        if self.is_over() {
             return;
         }
-        let next = match direction {
-            Direction::Down => Pos(0, 1),
-            Direction::Up => Pos(0, -1),
-            Direction::Left => Pos(-1, 0),
-            Direction::Right => Pos(1, 0),
-            _ => unreachable!(),
-        };
+        if self.steer(0, direction) && self.rival.is_none() {
+            self.step();
+        }
+    }
+
+    // point player one's snake in a new direction without stepping.
+    pub fn turn(&mut self, direction: Direction) {
+        self.steer(0, direction);
+    }
+
+    // point player two's snake in a new direction.
+    pub fn steer_rival(&mut self, direction: Direction) {
+        if self.rival.is_some() {
+            self.steer(1, direction);
+        }
+    }
+
+    // a snake can't turn back onto its own neck.
+    fn steer(&mut self, player: usize, direction: Direction) -> bool {
+        let snake = self.player(player);
+        let head_next_position = self.next_position(snake.positions.front().unwrap(), &direction);
+        if snake.positions.get(1) == Some(&head_next_position) {
+            return false;
+        }
+        self.player_mut(player).heading = direction;
+        true
+    }
+
+    // move every snake one cell along its heading.
+    fn step(&mut self) {
+        if self.is_over() {
+            return;
+        }
+        self.frame = 0;
+
+        let heads = self
+            .snakes()
+            .map(|snake| self.next_position(snake.positions.front().unwrap(), &snake.heading))
+            .collect::<Vec<Pos>>();
 
-        let head_next_position = self.snake.positions.front().unwrap() + &next;
+        let grows = heads.iter().map(|head| self.grows_at(head)).collect::<Vec<bool>>();
 
-        if self.snake.positions.get(1).unwrap() == &head_next_position {
+        let mut causes = heads
+            .iter()
+            .map(|head| {
+                if !self.is_in_bounds(*head) {
+                    Some(LossCause::Wall)
+                } else if self.obstacles.contains(head) {
+                    Some(LossCause::Obstacle)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<Option<LossCause>>>();
+
+        if let Some(rival) = &self.rival {
+            if heads[0] == heads[1] {
+                causes[0] = Some(LossCause::HeadOn);
+                causes[1] = Some(LossCause::HeadOn);
+            } else {
+                if rival.will_occupy(&heads[0], grows[1]) {
+                    causes[0] = causes[0].or(Some(LossCause::Rival));
+                }
+                if self.snake.will_occupy(&heads[1], grows[0]) {
+                    causes[1] = causes[1].or(Some(LossCause::Rival));
+                }
+            }
+        }
+
+        for (player, head) in heads.iter().enumerate() {
+            if causes[player].is_some() {
+                self.player_mut(player).is_dead = true;
+                continue;
+            }
+            let foods = std::mem::take(&mut self.foods);
+            self.player_mut(player).try_move(*head, &foods);
+            self.foods = foods;
+            if self.player(player).is_dead {
+                causes[player] = Some(LossCause::SelfCollision);
+            }
+        }
+
+        if let Some(cause) = causes.into_iter().flatten().next() {
+            self.status = GameStatus::Lost(cause);
             return;
         }
 
-        if self.is_in_bounds(head_next_position) {
-            self.snake.try_move(direction, &self.food);
-            if head_next_position == self.food.pos {
-                self.refresh_food();
+        for (player, head) in heads.iter().enumerate() {
+            if let Some(index) = self.foods.iter().position(|food| food.pos == *head) {
+                let food = self.foods.remove(index);
+                self.eat(player, food.kind);
+            }
+        }
+        // after eating, so food reached on its last step still counts.
+        self.age_food();
+        if self.is_over() {
+            return;
+        }
+        self.check_level_progress();
+        if self.rival.is_none()
+            && self.obstacles.len() + self.snake.positions.len() == (self.width * self.height) as usize
+        {
+            self.status = GameStatus::Won;
+            return;
+        }
+        self.fill_food();
+        self.age_pace();
+    }
+
+    fn grows_at(&self, pos: &Pos) -> bool {
+        self.foods.iter().any(|food| food.pos == *pos && food.kind.grows())
+    }
+
+    fn eat(&mut self, player: usize, kind: FoodKind) {
+        self.player_mut(player).score += kind.points();
+        match kind {
+            FoodKind::Normal | FoodKind::Bonus => {}
+            FoodKind::Shrink => self.player_mut(player).shrink(SHRINK_BY),
+            FoodKind::SpeedUp => self.set_pace(FAST_PACE),
+            FoodKind::SlowDown => self.set_pace(SLOW_PACE),
+            FoodKind::Poison => {
+                self.status = GameStatus::Lost(LossCause::Poison);
+                self.player_mut(player).is_dead = true;
+            }
+        }
+    }
+
+    fn set_pace(&mut self, pace: u32) {
+        self.pace = pace;
+        self.pace_steps_left = PACE_STEPS;
+    }
+
+    fn age_pace(&mut self) {
+        if self.pace_steps_left > 0 {
+            self.pace_steps_left -= 1;
+            if self.pace_steps_left == 0 {
+                self.pace = NORMAL_PACE;
+            }
+        }
+    }
+
+    // timed food disappears once its lifetime runs out, `fill_food` replaces it.
+    fn age_food(&mut self) {
+        self.foods.iter_mut().for_each(|food| {
+            if let Some(ttl) = food.ttl.as_mut() {
+                *ttl = ttl.saturating_sub(1);
             }
-        } else {
-            self.is_failed = true;
-            self.snake.is_dead = true;
+        });
+        self.foods.retain(|food| food.ttl != Some(0));
+    }
+
+    // the cell reached by stepping from `pos` towards `direction`,
+    // wrapped around the edges when the walls allow it.
+    pub fn next_position(&self, pos: &Pos, direction: &Direction) -> Pos {
+        let next = pos + &direction.delta();
+        match self.walls {
+            Walls::Solid => next,
+            Walls::Wrap => Pos(
+                next.0.rem_euclid(self.width as i32),
+                next.1.rem_euclid(self.height as i32),
+            ),
         }
     }
 
@@ -96,20 +461,334 @@ impl GameBoard<'_> {
     }
 
     pub fn get_position(&self, pos: &Pos) -> &str {
-        if self.food.pos == *pos {
-            return self.food.food_color;
+        if self.obstacles.contains(pos) {
+            return "🧱";
         }
-        if self.snake.positions.front().unwrap() == pos {
-            return self.snake.head_color;
+        if let Some(food) = self.foods.iter().find(|food| food.pos == *pos) {
+            return food.emoji();
         }
-        if self
-            .snake
-            .positions
-            .iter()
-            .any(|p| p.0 == pos.0 && p.1 == pos.1)
-        {
-            return self.snake.color;
+        for snake in self.snakes() {
+            if snake.positions.front().unwrap() == pos {
+                return snake.color.circle();
+            }
+            if snake.positions.iter().any(|p| p.0 == pos.0 && p.1 == pos.1) {
+                return snake.color.square();
+            }
         }
         ""
     }
 }
+
+#[cfg(test)]
+impl GameBoard {
+    // a seeded board holding just `snake` and `foods`, no more food spawns once they're eaten.
+    pub(crate) fn fixture(width: u32, height: u32, walls: Walls, snake: Vec<Pos>, foods: Vec<(Pos, FoodKind)>) -> Self {
+        Self {
+            width,
+            height,
+            walls,
+            obstacles: HashSet::new(),
+            level: None,
+            snake: Snake::new(snake),
+            rival: None,
+            status: GameStatus::Running,
+            foods: foods.into_iter().map(|(pos, kind)| Food::new(pos, kind, 0.0)).collect(),
+            food_count: 0,
+            pace: NORMAL_PACE,
+            pace_steps_left: 0,
+            frame: 0,
+            rng: ChaCha12Rng::seed_from_u64(0),
+        }
+    }
+}
+
+impl Game for GameBoard {
+    type Action = Action;
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn cell(&self, x: u32, y: u32) -> &str {
+        self.get_position(&Pos(x as i32, y as i32))
+    }
+
+    fn apply(&mut self, action: Action) {
+        match action {
+            Action::Move(direction) => self.try_move(direction),
+            Action::SteerRival(direction) => self.steer_rival(direction),
+        }
+    }
+
+    fn tick(&mut self) {
+        GameBoard::tick(self);
+    }
+
+    fn status(&self) -> Status {
+        match (self.status, self.winner()) {
+            (GameStatus::Running, _) => Status::Running,
+            (_, Some(Winner::PlayerOne)) => Status::Won(format!("{} wins!", self.snake.color.circle())),
+            (_, Some(Winner::PlayerTwo)) => {
+                Status::Won(format!("{} wins!", self.rival.as_ref().unwrap().color.circle()))
+            }
+            (_, Some(Winner::Draw)) => Status::Lost("Draw!".to_string()),
+            (GameStatus::Won, None) => Status::Won("🏆 You win!".to_string()),
+            (GameStatus::Lost(cause), None) => Status::Lost(format!("Game over: {}", cause)),
+        }
+    }
+
+    fn score(&self) -> String {
+        match &self.rival {
+            Some(rival) => format!(
+                "{} {}  {} {}",
+                self.snake.color.circle(), self.snake.score, rival.color.circle(), rival.score
+            ),
+            None => format!("score: {}", self.snake.score),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Direction, GameBoard, GameStatus, LossCause, Walls, Winner};
+    use crate::GameLib::color::Color;
+    use crate::SnakeLib::{food::{Food, FoodKind}, pos::Pos, snake::Snake};
+
+    // a 5x5 board with the snake in the middle row, heading left, and only the given food.
+    fn board(walls: Walls, foods: Vec<(Pos, FoodKind)>) -> GameBoard {
+        GameBoard::fixture(5, 5, walls, vec![Pos(0, 2), Pos(1, 2), Pos(2, 2)], foods)
+    }
+
+    #[test]
+    fn testSolidWallsAreLethal() {
+        let mut gameboard = board(Walls::Solid, vec![]);
+        gameboard.try_move(Direction::Left);
+
+        assert_eq!(gameboard.status, GameStatus::Lost(LossCause::Wall));
+        assert!(gameboard.snake.is_dead);
+    }
+
+    #[test]
+    fn testWrapWalls() {
+        let mut gameboard = board(Walls::Wrap, vec![]);
+        gameboard.try_move(Direction::Left);
+
+        assert_eq!(gameboard.status, GameStatus::Running);
+        assert_eq!(gameboard.snake.positions.front(), Some(&Pos(4, 2)));
+        assert_eq!(gameboard.snake.positions.len(), 3);
+
+        gameboard.try_move(Direction::Up);
+        gameboard.try_move(Direction::Up);
+        gameboard.try_move(Direction::Up);
+        assert_eq!(gameboard.snake.positions.front(), Some(&Pos(4, 4)));
+    }
+
+    #[test]
+    fn testObstaclesAreLethal() {
+        let mut gameboard = board(Walls::Wrap, vec![]);
+        gameboard.obstacles.insert(Pos(0, 1));
+        gameboard.try_move(Direction::Up);
+
+        assert_eq!(gameboard.status, GameStatus::Lost(LossCause::Obstacle));
+        assert!(gameboard.snake.is_dead);
+        assert_eq!(gameboard.get_position(&Pos(0, 1)), "🧱");
+    }
+
+    #[test]
+    fn testTick() {
+        let mut gameboard = board(Walls::Wrap, vec![]);
+        for _ in 0..gameboard.pace - 1 {
+            gameboard.tick();
+        }
+        assert_eq!(gameboard.snake.positions.front(), Some(&Pos(0, 2)));
+
+        gameboard.tick();
+        assert_eq!(gameboard.snake.positions.front(), Some(&Pos(4, 2)));
+    }
+
+    #[test]
+    fn testPoison() {
+        let mut gameboard = board(Walls::Solid, vec![(Pos(0, 1), FoodKind::Poison)]);
+        gameboard.try_move(Direction::Up);
+
+        assert_eq!(gameboard.status, GameStatus::Lost(LossCause::Poison));
+        assert_eq!(gameboard.snake.score, 0);
+    }
+
+    // timed food reached on its last step is eaten before it vanishes.
+    fn last_step_board(kind: FoodKind) -> GameBoard {
+        let mut gameboard = board(Walls::Solid, vec![(Pos(0, 1), kind)]);
+        gameboard.foods[0].ttl = Some(1);
+        gameboard.try_move(Direction::Up);
+        gameboard
+    }
+
+    #[test]
+    fn testBonusOnLastStep() {
+        let gameboard = last_step_board(FoodKind::Bonus);
+
+        assert_eq!(gameboard.snake.positions.len(), 4);
+        assert_eq!(gameboard.snake.score, 5);
+        assert!(gameboard.foods.is_empty());
+    }
+
+    #[test]
+    fn testPoisonOnLastStep() {
+        let gameboard = last_step_board(FoodKind::Poison);
+
+        assert_eq!(gameboard.status, GameStatus::Lost(LossCause::Poison));
+        assert!(gameboard.snake.is_dead);
+    }
+
+    #[test]
+    fn testShrink() {
+        let mut snake = Snake::new((0..6).map(|x| Pos(x, 0)).collect());
+        snake.shrink(3);
+        assert_eq!(snake.positions.len(), 3);
+        snake.shrink(3);
+        assert_eq!(snake.positions.len(), 2);
+    }
+
+    #[test]
+    fn testEatAnyFood() {
+        let mut gameboard = board(
+            Walls::Solid,
+            vec![(Pos(3, 3), FoodKind::Normal), (Pos(0, 1), FoodKind::Bonus)],
+        );
+        gameboard.try_move(Direction::Up);
+
+        assert_eq!(gameboard.snake.positions.len(), 4);
+        assert_eq!(gameboard.snake.score, 5);
+        assert_eq!(gameboard.foods.len(), 1);
+        assert_eq!(gameboard.get_position(&Pos(3, 3)), "🟥");
+        assert_eq!(gameboard.get_position(&Pos(0, 1)), gameboard.snake.color.circle());
+    }
+
+    // player two starts mirrored through the centre of a 5x5 board, facing right.
+    fn two_player_board() -> GameBoard {
+        let mut gameboard = GameBoard::fixture(5, 5, Walls::Solid, vec![Pos(1, 1), Pos(2, 1), Pos(3, 1)], vec![]);
+        gameboard.add_rival();
+        gameboard
+    }
+
+    #[test]
+    fn testAddRival() {
+        let gameboard = two_player_board();
+        let rival = gameboard.rival.as_ref().unwrap();
+
+        assert_eq!(rival.positions.front(), Some(&Pos(3, 3)));
+        assert_eq!(rival.heading, Direction::Right);
+        assert_eq!(gameboard.get_position(&Pos(3, 3)), "🔵");
+        assert_eq!(gameboard.winner(), None);
+    }
+
+    #[test]
+    fn testTwoPlayersWaitForTick() {
+        let mut gameboard = two_player_board();
+        gameboard.try_move(Direction::Down);
+
+        assert_eq!(gameboard.snake.positions.front(), Some(&Pos(1, 1)));
+        assert_eq!(gameboard.snake.heading, Direction::Down);
+    }
+
+    #[test]
+    fn testHeadToHead() {
+        let mut gameboard = two_player_board();
+        gameboard.try_move(Direction::Down);
+        gameboard.steer_rival(Direction::Up);
+        // both heads reach (1, 2) and (3, 2)...
+        for _ in 0..gameboard.pace {
+            gameboard.tick();
+        }
+        assert_eq!(gameboard.winner(), None);
+
+        // ...then meet in (2, 2).
+        gameboard.try_move(Direction::Right);
+        gameboard.steer_rival(Direction::Left);
+        for _ in 0..gameboard.pace {
+            gameboard.tick();
+        }
+        assert_eq!(gameboard.winner(), Some(Winner::Draw));
+        assert_eq!(gameboard.status, GameStatus::Lost(LossCause::HeadOn));
+    }
+
+    #[test]
+    fn testHeadToBody() {
+        let mut gameboard = two_player_board();
+        gameboard.rival = Some(Snake::with_color(
+            vec![Pos(2, 2), Pos(3, 2), Pos(4, 2)],
+            Direction::Left,
+            Color::Blue,
+        ));
+        gameboard.steer_rival(Direction::Up);
+        for _ in 0..gameboard.pace {
+            gameboard.tick();
+        }
+
+        assert_eq!(gameboard.winner(), Some(Winner::PlayerOne));
+    }
+
+    fn long_snake_board() -> GameBoard {
+        // a ring of four around (1, 1)-(2, 2), head at (1, 1) and tail at (1, 2).
+        GameBoard::fixture(5, 5, Walls::Solid, vec![Pos(1, 1), Pos(2, 1), Pos(2, 2), Pos(1, 2)], vec![])
+    }
+
+    #[test]
+    fn testChaseTail() {
+        let mut gameboard = long_snake_board();
+        gameboard.try_move(Direction::Down);
+
+        assert_eq!(gameboard.status, GameStatus::Running);
+        assert_eq!(gameboard.snake.positions.front(), Some(&Pos(1, 2)));
+    }
+
+    #[test]
+    fn testBiteSelf() {
+        let mut gameboard = long_snake_board();
+        // the tail stays put while the snake grows.
+        gameboard.foods = vec![Food::new(Pos(1, 2), FoodKind::Normal, 0.0)];
+        gameboard.try_move(Direction::Down);
+
+        assert_eq!(gameboard.status, GameStatus::Lost(LossCause::SelfCollision));
+        assert!(gameboard.is_over());
+    }
+
+    #[test]
+    fn testWin() {
+        let mut gameboard = GameBoard::fixture(
+            2,
+            2,
+            Walls::Solid,
+            vec![Pos(0, 0), Pos(1, 0), Pos(1, 1)],
+            vec![(Pos(0, 1), FoodKind::Normal)],
+        );
+        gameboard.try_move(Direction::Down);
+
+        assert_eq!(gameboard.status, GameStatus::Won);
+    }
+
+    #[test]
+    fn testNewBoard() {
+        let gameboard = GameBoard::with_level(0, Walls::Solid, 3).with_seed(7);
+
+        assert_eq!(gameboard.foods.len(), 3);
+        assert!(gameboard.foods.iter().all(|food| !gameboard.snake.positions.contains(&food.pos)));
+    }
+
+    #[test]
+    fn testLevelProgress() {
+        let mut gameboard = GameBoard::with_level(0, Walls::Solid, 1).with_seed(7);
+        let head = *gameboard.snake.positions.front().unwrap();
+        gameboard.snake = Snake::new((0..9).map(|n| Pos(head.0 + n, head.1)).collect());
+        gameboard.foods = vec![Food::new(Pos(head.0 - 1, head.1), FoodKind::Normal, 0.0)];
+        gameboard.try_move(Direction::Left);
+
+        assert_eq!(gameboard.level, Some(1));
+        assert_eq!(gameboard.snake.positions.len(), 3);
+        assert!(!gameboard.obstacles.is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+use std::{collections::HashSet, fmt, sync::OnceLock};
+
+use super::pos::Pos;
+
+// ASCII maps, one character per cell:
+//   `#` wall, `S` snake head (the body trails to the right), `.` empty.
+const OPEN_FIELD: &str = "
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............S...............
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+";
+
+const FOUR_PILLARS: &str = "
+..............................
+..............................
+..............................
+..............................
+..............................
+.....####............####.....
+.....####............####.....
+.....####............####.....
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+..............S...............
+..............................
+..............................
+..............................
+..............................
+..............................
+..............................
+.....####............####.....
+.....####............####.....
+.....####............####.....
+..............................
+..............................
+..............................
+..............................
+..............................
+";
+
+const CORRIDORS: &str = "
+##############################
+#............................#
+#............................#
+#....##################......#
+#............................#
+#............................#
+#............................#
+#......##################....#
+#............................#
+#............................#
+#............................#
+#....##################......#
+#............................#
+#............................#
+#.............S..............#
+#............................#
+#............................#
+#......##################....#
+#............................#
+#............................#
+#............................#
+#....##################......#
+#............................#
+#............................#
+#............................#
+#......##################....#
+#............................#
+#............................#
+#............................#
+##############################
+";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LevelError {
+    Empty,
+    RaggedLine(usize),
+    UnknownCell(char, Pos),
+    MissingStart,
+    BlockedStart,
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::Empty => write!(f, "the map has no cells"),
+            LevelError::RaggedLine(y) => write!(f, "line {} has a different width", y),
+            LevelError::UnknownCell(c, pos) => write!(f, "unknown cell {:?} at {:?}", c, pos),
+            LevelError::MissingStart => write!(f, "the map has no `S` start cell"),
+            LevelError::BlockedStart => write!(f, "there is no room for the snake right of `S`"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Level {
+    pub width: u32,
+    pub height: u32,
+    pub obstacles: HashSet<Pos>,
+    pub start: Pos,
+    // snake length that moves the player on to the next level.
+    pub target_length: usize,
+}
+
+impl Level {
+    pub fn parse(map: &str, target_length: usize) -> Result<Self, LevelError> {
+        let lines: Vec<&str> = map
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        let width = lines.first().ok_or(LevelError::Empty)?.chars().count();
+        let mut obstacles = HashSet::new();
+        let mut start = None;
+
+        for (y, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(LevelError::RaggedLine(y));
+            }
+            for (x, c) in line.chars().enumerate() {
+                let pos = Pos(x as i32, y as i32);
+                match c {
+                    '#' => {
+                        obstacles.insert(pos);
+                    }
+                    'S' => start = Some(pos),
+                    '.' => {}
+                    _ => return Err(LevelError::UnknownCell(c, pos)),
+                }
+            }
+        }
+
+        let start = start.ok_or(LevelError::MissingStart)?;
+        let level = Self {
+            width: width as u32,
+            height: lines.len() as u32,
+            obstacles,
+            start,
+            target_length,
+        };
+
+        if level.start_positions().iter().any(|pos| {
+            pos.0 >= level.width as i32 || level.obstacles.contains(pos)
+        }) {
+            return Err(LevelError::BlockedStart);
+        }
+
+        Ok(level)
+    }
+
+    // head first, like `Snake::positions`.
+    pub fn start_positions(&self) -> Vec<Pos> {
+        (0..3).map(|n| Pos(self.start.0 + n, self.start.1)).collect()
+    }
+}
+
+// parsed on first use, boards look their level up on every step.
+pub fn levels() -> &'static [Level] {
+    static LEVELS: OnceLock<Vec<Level>> = OnceLock::new();
+    LEVELS.get_or_init(|| {
+        [(OPEN_FIELD, 10), (FOUR_PILLARS, 15), (CORRIDORS, 20)]
+            .iter()
+            .map(|(map, target_length)| Level::parse(map, *target_length).expect("built-in level should parse"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{levels, Level, LevelError};
+    use crate::SnakeLib::pos::Pos;
+
+    #[test]
+    fn testParse() {
+        let level = Level::parse("
+            #####
+            #S..#
+            #####
+        ", 5).unwrap();
+
+        assert_eq!(level.width, 5);
+        assert_eq!(level.height, 3);
+        assert_eq!(level.start, Pos(1, 1));
+        assert_eq!(level.obstacles.len(), 12);
+        assert!(level.obstacles.contains(&Pos(4, 1)));
+    }
+
+    #[test]
+    fn testParseErrors() {
+        assert_eq!(Level::parse("", 5).unwrap_err(), LevelError::Empty);
+        assert_eq!(Level::parse("S..\n..", 5).unwrap_err(), LevelError::RaggedLine(1));
+        assert_eq!(Level::parse("S..x", 5).unwrap_err(), LevelError::UnknownCell('x', Pos(3, 0)));
+        assert_eq!(Level::parse("....", 5).unwrap_err(), LevelError::MissingStart);
+        assert_eq!(Level::parse("S.#.", 5).unwrap_err(), LevelError::BlockedStart);
+    }
+
+    #[test]
+    fn testBuiltInLevels() {
+        assert_eq!(levels().len(), 3);
+        // parsed once.
+        assert!(std::ptr::eq(levels(), levels()));
+    }
+}
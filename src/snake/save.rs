@@ -0,0 +1,289 @@
+// saving a game in progress, see `StateLib::persist`.
+use super::{gameboard::GameBoard, level::levels, pos::Pos};
+use crate::StateLib::persist::Persist;
+
+impl Persist for GameBoard {
+    fn encode(&self) -> String {
+        codec::encode(self)
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        codec::decode(text).filter(is_playable)
+    }
+}
+
+// a save that decodes can still hold a board no game reaches, e.g. one edited by hand.
+fn is_playable(board: &GameBoard) -> bool {
+    let on_board =
+        |pos: &Pos| pos.0 >= 0 && pos.1 >= 0 && (pos.0 as u32) < board.width && (pos.1 as u32) < board.height;
+    board.level.is_none_or(|index| index < levels().len())
+        && board
+            .snakes()
+            .all(|snake| !snake.positions.is_empty() && snake.positions.iter().all(on_board))
+        && board.foods.iter().all(|food| on_board(&food.pos))
+}
+
+// with serde the board is saved through its derives, so there's only ever one format.
+#[cfg(feature = "serde")]
+mod codec {
+    pub use crate::StateLib::persist::{decode_json as decode, encode_json as encode};
+}
+
+// written out by hand without it.
+#[cfg(not(feature = "serde"))]
+mod codec {
+    use crate::{
+        SnakeLib::{
+            food::{Food, FoodKind},
+            gameboard::{Direction, GameBoard, GameStatus, LossCause, Walls},
+            pos::Pos,
+            snake::Snake,
+        },
+        StateLib::persist::{pack, unpack, Persist},
+    };
+
+    // enums are saved by name.
+    macro_rules! persist_by_name {
+        ( $t:ty { $( $variant:path => $name:literal ),* $(,)? } ) => {
+            impl Persist for $t {
+                fn encode(&self) -> String {
+                    match self {
+                        $( $variant => $name, )*
+                    }
+                    .to_string()
+                }
+
+                fn decode(text: &str) -> Option<Self> {
+                    match text {
+                        $( $name => Some($variant), )*
+                        _ => None,
+                    }
+                }
+            }
+        };
+    }
+
+    persist_by_name!(Direction {
+        Direction::Up => "up",
+        Direction::Down => "down",
+        Direction::Left => "left",
+        Direction::Right => "right",
+    });
+
+    persist_by_name!(Walls {
+        Walls::Solid => "solid",
+        Walls::Wrap => "wrap",
+    });
+
+    persist_by_name!(FoodKind {
+        FoodKind::Normal => "normal",
+        FoodKind::Bonus => "bonus",
+        FoodKind::Shrink => "shrink",
+        FoodKind::SpeedUp => "speed-up",
+        FoodKind::SlowDown => "slow-down",
+        FoodKind::Poison => "poison",
+    });
+
+    persist_by_name!(LossCause {
+        LossCause::Wall => "wall",
+        LossCause::Obstacle => "obstacle",
+        LossCause::SelfCollision => "self-collision",
+        LossCause::Rival => "rival",
+        LossCause::HeadOn => "head-on",
+        LossCause::Poison => "poison",
+    });
+
+    impl Persist for GameStatus {
+        fn encode(&self) -> String {
+            match self {
+                GameStatus::Running => "running".to_string(),
+                GameStatus::Won => "won".to_string(),
+                GameStatus::Lost(cause) => format!("lost:{}", cause.encode()),
+            }
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            match text {
+                "running" => Some(GameStatus::Running),
+                "won" => Some(GameStatus::Won),
+                _ => LossCause::decode(text.strip_prefix("lost:")?).map(GameStatus::Lost),
+            }
+        }
+    }
+
+    impl Persist for Pos {
+        fn encode(&self) -> String {
+            format!("{},{}", self.0, self.1)
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            let (x, y) = text.split_once(',')?;
+            Some(Pos(x.parse().ok()?, y.parse().ok()?))
+        }
+    }
+
+    impl Persist for Snake {
+        fn encode(&self) -> String {
+            let positions = self.positions.iter().copied().collect::<Vec<Pos>>();
+            pack(&[
+                positions.encode(),
+                self.is_dead.encode(),
+                self.heading.encode(),
+                self.score.encode(),
+                self.color.encode(),
+            ])
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            let [positions, is_dead, heading, score, color] = unpack(text)?[..] else {
+                return None;
+            };
+            let mut snake = Snake::with_color(
+                Persist::decode(positions)?,
+                Persist::decode(heading)?,
+                Persist::decode(color)?,
+            );
+            snake.is_dead = Persist::decode(is_dead)?;
+            snake.score = Persist::decode(score)?;
+            Some(snake)
+        }
+    }
+
+    impl Persist for Food {
+        fn encode(&self) -> String {
+            pack(&[
+                self.color.encode(),
+                self.pos.encode(),
+                self.kind.encode(),
+                self.ttl.encode(),
+            ])
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            let [color, pos, kind, ttl] = unpack(text)?[..] else {
+                return None;
+            };
+            Some(Food {
+                color: Persist::decode(color)?,
+                pos: Persist::decode(pos)?,
+                kind: Persist::decode(kind)?,
+                ttl: Persist::decode(ttl)?,
+            })
+        }
+    }
+
+    pub fn encode(board: &GameBoard) -> String {
+        let obstacles = board.obstacles.iter().copied().collect::<Vec<Pos>>();
+        pack(&[
+            board.width.encode(),
+            board.height.encode(),
+            board.walls.encode(),
+            obstacles.encode(),
+            board.level.encode(),
+            board.snake.encode(),
+            board.rival.encode(),
+            board.status.encode(),
+            board.foods.encode(),
+            board.food_count.encode(),
+            board.pace.encode(),
+            board.pace_steps_left.encode(),
+            board.frame.encode(),
+            board.rng.encode(),
+        ])
+    }
+
+    pub fn decode(text: &str) -> Option<GameBoard> {
+        let [width, height, walls, obstacles, level, snake, rival, status, foods, food_count, pace, pace_steps_left, frame, rng] =
+            unpack(text)?[..]
+        else {
+            return None;
+        };
+        Some(GameBoard {
+            width: Persist::decode(width)?,
+            height: Persist::decode(height)?,
+            walls: Persist::decode(walls)?,
+            obstacles: Vec::<Pos>::decode(obstacles)?.into_iter().collect(),
+            level: Persist::decode(level)?,
+            snake: Persist::decode(snake)?,
+            rival: Persist::decode(rival)?,
+            status: Persist::decode(status)?,
+            foods: Persist::decode(foods)?,
+            food_count: Persist::decode(food_count)?,
+            pace: Persist::decode(pace)?,
+            pace_steps_left: Persist::decode(pace_steps_left)?,
+            frame: Persist::decode(frame)?,
+            rng: Persist::decode(rng)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        GameLib::Game,
+        SnakeLib::{
+            autopilot,
+            gameboard::{GameBoard, Walls},
+        },
+        SnakeLib::{food::{Food, FoodKind}, pos::Pos},
+        StateLib::persist::{deserialize, serialize},
+    };
+
+    fn level_board() -> GameBoard {
+        let mut board = GameBoard::with_level(1, Walls::Wrap, 3).with_seed(5);
+        board.add_rival();
+        board
+    }
+
+    fn resumes(board: &GameBoard) -> bool {
+        deserialize::<GameBoard>(&serialize(board)).is_some()
+    }
+
+    #[test]
+    fn testSaveAndResume() {
+        let mut board = level_board();
+        (0..300).for_each(|_| autopilot::tick(&mut board));
+
+        let mut resumed: GameBoard = deserialize(&serialize(&board)).unwrap();
+        assert_eq!(resumed.cells(), board.cells());
+        assert_eq!(resumed.score(), board.score());
+
+        // the same food keeps spawning in the same places.
+        (0..300).for_each(|_| {
+            autopilot::tick(&mut board);
+            autopilot::tick(&mut resumed);
+        });
+        assert_eq!(resumed.cells(), board.cells());
+        assert_eq!(resumed.status(), board.status());
+    }
+
+    #[test]
+    fn testRejectUnknownLevel() {
+        let mut board = level_board();
+        assert!(resumes(&board));
+        board.level = Some(99);
+        assert!(!resumes(&board));
+    }
+
+    #[test]
+    fn testRejectEmptySnake() {
+        let mut board = level_board();
+        board.snake.positions.clear();
+        assert!(!resumes(&board));
+
+        let mut board = level_board();
+        board.rival.as_mut().unwrap().positions.clear();
+        assert!(!resumes(&board));
+    }
+
+    #[test]
+    fn testRejectOffBoard() {
+        let mut board = level_board();
+        board.snake.positions.push_back(Pos(board.width as i32, 0));
+        assert!(!resumes(&board));
+
+        let mut board = level_board();
+        board.foods.push(Food::new(Pos(-1, 0), FoodKind::Normal, 0.0));
+        assert!(!resumes(&board));
+    }
+}
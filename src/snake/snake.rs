@@ -1,18 +1,22 @@
-use std::{collections::VecDeque, ops::Add, mem::swap};
-use super::{pos::Pos, gameboard::Direction, food::Food};
+use std::{collections::VecDeque, ops::Add};
+use super::{pos::Pos, food::Food, gameboard::Direction};
+use crate::GameLib::color::Color;
 
 
 
 
 #[derive(Debug, Clone)]
-pub struct Snake<'a> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snake {
     pub positions: VecDeque<Pos>,
     pub is_dead: bool,
-    pub color: &'a str,
-    pub head_color: &'a str,
+    pub heading: Direction,
+    pub score: u32,
+    // the body is drawn in squares, the head in a circle of it.
+    pub color: Color,
 }
 
-impl Add<Pos> for Snake<'_> {
+impl Add<Pos> for Snake {
     type Output = Self;
     fn add(self, rhs: Pos) -> Self::Output {
         let next_head = self.positions.front().unwrap() + &rhs;
@@ -26,42 +30,58 @@ impl Add<Pos> for Snake<'_> {
 }
 
 
-impl Snake<'_> {
+impl Snake {
+    // the body trails to the right of the head, so a new snake heads left.
     pub fn new(positions: Vec<Pos>) -> Self {
+        Self::with_color(positions, Direction::Left, Color::Red)
+    }
+
+    pub fn with_color(positions: Vec<Pos>, heading: Direction, color: Color) -> Self {
         Self {
             positions: VecDeque::from(positions),
             is_dead: false,
-            color: "🟥",
-            head_color: "🔴"
+            heading,
+            score: 0,
+            color,
         }
     }
 
-    pub fn try_move(&mut self, direction: Direction, food: &Food) {
-        let next =  match direction {
-            Direction::Down => Pos(0, 1),
-            Direction::Up => Pos(0, -1),
-            Direction::Left => Pos(-1, 0),
-            Direction::Right => Pos(1, 0),
-            _ => unreachable!()
-        };
-
-        let head_next_position = self.positions.front().unwrap() + &next;
-
-        if self.is_eat_self(&head_next_position) {
+    pub fn try_move(&mut self, head_next_position: Pos, foods: &[Food]) {
+        let grows = foods
+            .iter()
+            .any(|food| food.pos == head_next_position && food.kind.grows());
+        if self.is_eat_self(&head_next_position, grows) {
             self.is_dead = true;
             return;
         }
 
         let mut new_positions = self.positions.clone();
         new_positions.push_front(head_next_position);
-        if head_next_position != food.pos {
+        if !grows {
             new_positions.pop_back();
         } 
         self.positions = new_positions;
     }
 
-    pub fn is_eat_self(&self, head_next_position: &Pos) -> bool {
-        self.positions.contains(head_next_position)
+    // drop up to `n` tail segments, a snake never gets shorter than two.
+    pub fn shrink(&mut self, n: usize) {
+        let len = self.positions.len().saturating_sub(n).max(2);
+        self.positions.truncate(len);
+    }
+
+    pub fn is_eat_self(&self, head_next_position: &Pos, grows: bool) -> bool {
+        self.will_occupy(head_next_position, grows)
+    }
+
+    // whether `pos` is still covered after the next step,
+    // the tail moves out of its cell unless the snake is growing.
+    pub fn will_occupy(&self, pos: &Pos, grows: bool) -> bool {
+        let len = if grows {
+            self.positions.len()
+        } else {
+            self.positions.len() - 1
+        };
+        self.positions.iter().take(len).any(|p| p == pos)
     }
 
 }
@@ -0,0 +1,122 @@
+use crate::DomLib::{
+    domtools::DomElements,
+    grid::{default_save_key, run_grid, Autoplay, GridOptions, Mount, Renderer, SaveKey},
+};
+#[cfg(feature = "serde")]
+use crate::StateLib::persist::encode_json;
+use wasm_bindgen::prelude::*;
+use web_sys::Element;
+
+use super::{
+    autopilot,
+    gameboard::{Action, Direction, GameBoard, Walls},
+};
+
+// the board advances one frame per tick, see `GameBoard::tick`.
+const FRAME_MS: f64 = 50.0;
+// room for the starting snake.
+const MIN_SIZE: u32 = 5;
+
+// without a size the game plays through the levels, with one it's an open field.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Default)]
+pub struct SnakeOptions {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    // the board's edges wrap around.
+    pub wrap_walls: bool,
+    // food on the board at once, 1 by default.
+    pub food_count: Option<usize>,
+    // a second snake steered with WASD.
+    pub two_players: bool,
+    // adds a `theme-<name>` class to the container.
+    pub theme: Option<String>,
+    pub renderer: Renderer,
+    // where an unfinished game is saved and resumed from,
+    // `"snake-<target id>"` by default, or `"snake"` for a target without an id.
+    pub save_key: Option<String>,
+    // ignore a saved game and start a new one in its place.
+    pub new_game: bool,
+}
+
+#[wasm_bindgen]
+impl SnakeOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn keymap(code: &str) -> Option<Action> {
+    match code {
+        "ArrowLeft" => Some(Action::Move(Direction::Left)),
+        "ArrowRight" => Some(Action::Move(Direction::Right)),
+        "ArrowUp" => Some(Action::Move(Direction::Up)),
+        "ArrowDown" => Some(Action::Move(Direction::Down)),
+        // player two
+        "KeyW" => Some(Action::SteerRival(Direction::Up)),
+        "KeyA" => Some(Action::SteerRival(Direction::Left)),
+        "KeyS" => Some(Action::SteerRival(Direction::Down)),
+        "KeyD" => Some(Action::SteerRival(Direction::Right)),
+        _ => None,
+    }
+}
+
+pub fn run_snake(domElements: &DomElements, target: &Element, options: &SnakeOptions) -> Mount {
+    let walls = if options.wrap_walls { Walls::Wrap } else { Walls::Solid };
+    let food_count = options.food_count.unwrap_or(1);
+    let save_key = SaveKey::claim(
+        &options
+            .save_key
+            .clone()
+            .unwrap_or_else(|| default_save_key("snake", target)),
+    );
+    // the options only shape a new game, a saved one resumes as it was.
+    let saved = save_key.as_ref().filter(|_| !options.new_game).and_then(SaveKey::load);
+    let gameboard = saved.unwrap_or_else(|| {
+        let mut gameboard = match (options.width, options.height) {
+            (None, None) => GameBoard::with_level(0, walls, food_count),
+            (width, height) => GameBoard::new(
+                width.unwrap_or(30).max(MIN_SIZE),
+                height.unwrap_or(30).max(MIN_SIZE),
+                walls,
+                food_count,
+            ),
+        };
+        if options.two_players {
+            gameboard.add_rival();
+        }
+        gameboard
+    });
+
+    let mut class_name = match gameboard.walls {
+        Walls::Solid => "snake-container",
+        Walls::Wrap => "snake-container wrap",
+    }
+    .to_string();
+    if let Some(theme) = &options.theme {
+        class_name.push_str(&format!(" theme-{}", theme));
+    }
+
+    run_grid(
+        domElements,
+        target,
+        gameboard,
+        GridOptions {
+            class_name,
+            renderer: options.renderer,
+            save_key,
+            #[cfg(feature = "serde")]
+            to_json: Some(encode_json),
+            #[cfg(not(feature = "serde"))]
+            to_json: None,
+            step_ms: FRAME_MS,
+            keymap,
+            // press `P` to let the autopilot play player one.
+            autoplay: Some(Autoplay {
+                key: "KeyP",
+                play: autopilot::tick,
+            }),
+        },
+    )
+}
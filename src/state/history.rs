@@ -0,0 +1,166 @@
+use std::{cell::Ref, collections::VecDeque};
+
+use crate::StateLib::{
+    ref_container::RefContainer,
+    state::{use_state, State, StateError, Subscription},
+};
+
+#[derive(Debug)]
+struct Snapshots<T> {
+    past: VecDeque<T>,
+    future: Vec<T>,
+}
+
+// a `State` that keeps the values it had before each `set`, to go back and forth through them.
+#[derive(Debug)]
+pub struct History<T: Clone> {
+    state: State<T>,
+    snapshots: RefContainer<Snapshots<T>>,
+    // how many values `undo` can go back, the oldest are dropped first.
+    capacity: usize,
+}
+
+impl<T: Clone> History<T> {
+    pub fn value(&self) -> Ref<'_, T> {
+        self.state.value()
+    }
+
+    // a new value drops whatever `redo` could have brought back.
+    pub fn set(&mut self, f: impl FnOnce(T) -> T) {
+        if let Err(error) = self.try_set(f) {
+            panic!("Cannot set state: {}", error);
+        }
+    }
+
+    // the history only moves on once the state did, a failed or panicking `set` leaves it as it was.
+    pub fn try_set(&mut self, f: impl FnOnce(T) -> T) -> Result<(), StateError> {
+        let current = self.state.try_value()?.clone();
+        self.state.try_set(f)?;
+
+        let mut snapshots = self.snapshots.current_mut();
+        snapshots.future.clear();
+        if self.capacity > 0 {
+            if snapshots.past.len() == self.capacity {
+                snapshots.past.pop_front();
+            }
+            snapshots.past.push_back(current);
+        }
+        Ok(())
+    }
+
+    // false when there's nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.snapshots.current_mut().past.pop_back() else {
+            return false;
+        };
+        let current = self.state.value().clone();
+        self.snapshots.current_mut().future.push(current);
+        self.state.set(|_| previous);
+        true
+    }
+
+    // false when there's nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.snapshots.current_mut().future.pop() else {
+            return false;
+        };
+        let current = self.state.value().clone();
+        self.snapshots.current_mut().past.push_back(current);
+        self.state.set(|_| next);
+        true
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.snapshots.current().past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.snapshots.current().future.is_empty()
+    }
+
+    // `f` gets the new value after every `set`, `undo` and `redo`.
+    pub fn subscribe(&self, f: impl Fn(&T) + 'static) -> Subscription<T> {
+        self.state.subscribe(f)
+    }
+}
+
+impl<T: Clone> Clone for History<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            snapshots: self.snapshots.clone(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+pub fn use_history<T: Clone>(capacity: usize, f: impl FnOnce() -> T) -> History<T> {
+    History {
+        state: use_state(f),
+        snapshots: RefContainer::new(Snapshots {
+            past: VecDeque::with_capacity(capacity),
+            future: vec![],
+        }),
+        capacity,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use super::use_history;
+    use crate::StateLib::state::StateError;
+
+    #[test]
+    fn testUndoRedo() {
+        let mut text = use_history(10, String::new);
+        text.set(|t| t + "a");
+        text.set(|t| t + "b");
+        text.set(|t| t + "c");
+
+        assert!(text.undo());
+        assert!(text.undo());
+        assert_eq!(*text.value(), "a");
+        assert!(text.redo());
+        assert_eq!(*text.value(), "ab");
+
+        // a new value after undo starts a new branch.
+        text.set(|t| t + "x");
+        assert!(!text.can_redo());
+        assert!(!text.redo());
+        assert!(text.undo());
+        assert!(text.undo());
+        assert_eq!(*text.value(), "a");
+    }
+
+    #[test]
+    fn testFailedSet() {
+        let mut count = use_history(10, || 0);
+        count.set(|n| n + 1);
+        count.set(|n| n + 1);
+        assert!(count.undo());
+
+        let value = count.value();
+        assert_eq!(count.clone().try_set(|n| n + 5), Err(StateError::Borrowed));
+        drop(value);
+        assert!(count.can_redo());
+
+        let result = catch_unwind(AssertUnwindSafe(|| count.set(|_| panic!("oops"))));
+        assert!(result.is_err());
+        // still one step back to 0 and one forward to 2.
+        assert!(count.can_undo());
+        assert!(count.can_redo());
+    }
+
+    #[test]
+    fn testCapacity() {
+        let mut count = use_history(3, || 0);
+        (0..10).for_each(|_| count.set(|n| n + 1));
+
+        while count.undo() {}
+        assert_eq!(*count.value(), 7);
+        while count.redo() {}
+        assert_eq!(*count.value(), 10);
+    }
+}
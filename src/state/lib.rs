@@ -1,3 +1,6 @@
+pub mod history;
+pub mod memo;
 pub mod persist;
+pub mod reducer;
 pub mod ref_container;
 pub mod state;
\ No newline at end of file
@@ -0,0 +1,89 @@
+use std::cell::Ref;
+
+use crate::StateLib::state::{use_state, State, Subscription};
+
+// a value computed from a `State`, see `use_memo`.
+#[derive(Debug)]
+pub struct Memo<T> {
+    state: State<T>,
+}
+
+impl<T> Memo<T> {
+    pub fn value(&self) -> Ref<'_, T> {
+        self.state.value()
+    }
+
+    // `f` only fires when the computed value changes, not on every `set` of the source.
+    pub fn subscribe(&self, f: impl Fn(&T) + 'static) -> Subscription<T>
+    where
+        T: Clone,
+    {
+        self.state.subscribe(f)
+    }
+}
+
+impl<T> Clone for Memo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+// `f` of `source`, cached and recomputed after each `set` of `source`.
+pub fn use_memo<S, T>(source: &State<S>, f: impl Fn(&S) -> T + 'static) -> Memo<T>
+where
+    S: Clone + 'static,
+    T: PartialEq + 'static,
+{
+    let state = use_state(|| f(&source.value()));
+
+    let memo = state.clone();
+    let _ = source.subscribe(move |source| {
+        let value = f(source);
+        if *memo.value() != value {
+            memo.clone().set(|_| value);
+        }
+    });
+
+    Memo { state }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::use_memo;
+    use crate::StateLib::state::use_state;
+
+    #[test]
+    fn testMemo() {
+        let mut pair = use_state(|| (1, 10));
+        let computed = Rc::new(Cell::new(0));
+        let fired = Rc::new(Cell::new(0));
+
+        let first = {
+            let computed = computed.clone();
+            use_memo(&pair, move |(a, _)| {
+                computed.set(computed.get() + 1);
+                a * 2
+            })
+        };
+        {
+            let fired = fired.clone();
+            let _ = first.subscribe(move |_| fired.set(fired.get() + 1));
+        }
+        assert_eq!(*first.value(), 2);
+        // reading doesn't recompute.
+        assert_eq!(*first.value(), 2);
+        assert_eq!(computed.get(), 1);
+
+        pair.set(|(a, b)| (a, b + 1));
+        assert_eq!(computed.get(), 2);
+        assert_eq!(fired.get(), 0);
+
+        pair.set(|(a, b)| (a + 1, b));
+        assert_eq!(*first.value(), 4);
+        assert_eq!(fired.get(), 1);
+    }
+}
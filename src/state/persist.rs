@@ -1,8 +1,305 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-pub struct PersistedOrigin;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
 
-pub trait Persist: Clone + 'static {
-    fn ptr(&self) -> PersistedOrigin {
-        PersistedOrigin
+use crate::StateLib::state::{use_state, State};
+
+// a value that can be saved as text and read back.
+pub trait Persist: Sized {
+    // bump when `encode` changes, values saved by another version are ignored.
+    const VERSION: u32 = 1;
+
+    fn encode(&self) -> String;
+    fn decode(text: &str) -> Option<Self>;
+}
+
+macro_rules! persist_from_str {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Persist for $t {
+                fn encode(&self) -> String {
+                    self.to_string()
+                }
+
+                fn decode(text: &str) -> Option<Self> {
+                    text.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+persist_from_str!(bool, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, String);
+
+// several encoded values in one, each prefixed with its length: `3:abc2:de`.
+pub fn pack<S: AsRef<str>>(parts: &[S]) -> String {
+    parts
+        .iter()
+        .map(|part| format!("{}:{}", part.as_ref().len(), part.as_ref()))
+        .collect()
+}
+
+pub fn unpack(mut text: &str) -> Option<Vec<&str>> {
+    let mut parts = vec![];
+    while !text.is_empty() {
+        let (len, rest) = text.split_once(':')?;
+        let len = len.parse::<usize>().ok()?;
+        parts.push(rest.get(..len)?);
+        text = rest.get(len..)?;
+    }
+    Some(parts)
+}
+
+impl<T: Persist> Persist for Vec<T> {
+    fn encode(&self) -> String {
+        pack(&self.iter().map(T::encode).collect::<Vec<String>>())
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        unpack(text)?.into_iter().map(T::decode).collect()
+    }
+}
+
+// `None` is no part at all, `Some` is one.
+impl<T: Persist> Persist for Option<T> {
+    fn encode(&self) -> String {
+        pack(&self.iter().map(T::encode).collect::<Vec<String>>())
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        match unpack(text)?[..] {
+            [] => Some(None),
+            [value] => T::decode(value).map(Some),
+            _ => None,
+        }
+    }
+}
+
+// the seed and how far the generator got, so it carries on with the same numbers.
+impl Persist for ChaCha12Rng {
+    fn encode(&self) -> String {
+        let seed = self.get_seed().iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+        pack(&[seed, self.get_stream().to_string(), self.get_word_pos().to_string()])
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        let [seed, stream, word_pos] = unpack(text)?[..] else {
+            return None;
+        };
+        let mut bytes = [0u8; 32];
+        if seed.len() != 64 {
+            return None;
+        }
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(seed.get(i * 2..i * 2 + 2)?, 16).ok()?;
+        }
+        let mut rng = ChaCha12Rng::from_seed(bytes);
+        rng.set_stream(stream.parse().ok()?);
+        rng.set_word_pos(word_pos.parse().ok()?);
+        Some(rng)
+    }
+}
+
+// stored as `v<VERSION>:<encoded>`.
+pub fn serialize<T: Persist>(value: &T) -> String {
+    format!("v{}:{}", T::VERSION, value.encode())
+}
+
+// `None` for text that isn't in the format, or was saved by another version of `T`.
+pub fn deserialize<T: Persist>(text: &str) -> Option<T> {
+    let (version, encoded) = text.strip_prefix('v')?.split_once(':')?;
+    if version.parse::<u32>().ok()? != T::VERSION {
+        return None;
+    }
+    T::decode(encoded)
+}
+
+// `Persist::encode` / `decode` for types that derive serde, as JSON.
+#[cfg(feature = "serde")]
+pub fn encode_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("engine types serialize to JSON")
+}
+
+#[cfg(feature = "serde")]
+pub fn decode_json<T: serde::de::DeserializeOwned>(text: &str) -> Option<T> {
+    serde_json::from_str(text).ok()
+}
+
+// where persisted values are kept.
+pub trait Storage {
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&self, key: &str, value: &str);
+    fn remove(&self, key: &str);
+}
+
+// gone when the last clone is dropped, for native builds and tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    items: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.items.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.items.borrow_mut().insert(key.to_string(), value.to_string());
+    }
+
+    fn remove(&self, key: &str) {
+        self.items.borrow_mut().remove(key);
+    }
+}
+
+// the browser's localStorage or sessionStorage. Writes fail quietly, e.g. when the quota is full.
+#[cfg(feature = "web")]
+#[derive(Debug, Clone)]
+pub struct WebStorage(web_sys::Storage);
+
+#[cfg(feature = "web")]
+impl WebStorage {
+    pub fn local() -> Option<Self> {
+        web_sys::window()?.local_storage().ok().flatten().map(Self)
+    }
+
+    pub fn session() -> Option<Self> {
+        web_sys::window()?.session_storage().ok().flatten().map(Self)
+    }
+}
+
+#[cfg(feature = "web")]
+impl Storage for WebStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.get_item(key).ok().flatten()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        let _ = self.0.set_item(key, value);
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = self.0.remove_item(key);
+    }
+}
+
+// the value saved under `key`, if there's a readable one.
+pub fn load<T: Persist>(storage: &dyn Storage, key: &str) -> Option<T> {
+    deserialize(&storage.get(key)?)
+}
+
+pub fn save<T: Persist>(storage: &dyn Storage, key: &str, value: &T) {
+    storage.set(key, &serialize(value));
+}
+
+thread_local! {
+    static MEMORY: MemoryStorage = MemoryStorage::new();
+}
+
+// localStorage in the browser when it's available, memory otherwise.
+pub fn default_storage() -> Rc<dyn Storage> {
+    #[cfg(feature = "web")]
+    if let Some(storage) = WebStorage::local() {
+        return Rc::new(storage);
+    }
+    Rc::new(MEMORY.with(MemoryStorage::clone))
+}
+
+// a state loaded from `key` in the default storage, saved there after every `set`.
+pub fn use_persisted_state<T: Persist + Clone + 'static>(key: &str, default: impl FnOnce() -> T) -> State<T> {
+    use_persisted_state_in(default_storage(), key, default)
+}
+
+// `default` when nothing readable is saved under `key`.
+pub fn use_persisted_state_in<T: Persist + Clone + 'static>(
+    storage: Rc<dyn Storage>,
+    key: &str,
+    default: impl FnOnce() -> T,
+) -> State<T> {
+    let state = use_state(|| load(&*storage, key).unwrap_or_else(default));
+
+    let key = key.to_string();
+    let _ = state.subscribe(move |value| save(&*storage, &key, value));
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    use super::{deserialize, pack, serialize, unpack, use_persisted_state_in, MemoryStorage, Persist, Storage};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point(i32, i32);
+
+    impl Persist for Point {
+        const VERSION: u32 = 2;
+
+        fn encode(&self) -> String {
+            format!("{},{}", self.0, self.1)
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            let (x, y) = text.split_once(',')?;
+            Some(Point(x.parse().ok()?, y.parse().ok()?))
+        }
+    }
+
+    #[test]
+    fn testFormat() {
+        assert_eq!(serialize(&Point(1, -2)), "v2:1,-2");
+        assert_eq!(deserialize("v2:1,-2"), Some(Point(1, -2)));
+        assert_eq!(deserialize::<Point>("v1:1,-2"), None);
+        assert_eq!(deserialize::<Point>("1,-2"), None);
+        assert_eq!(deserialize::<String>(&serialize(&"a:b".to_string())), Some("a:b".to_string()));
+    }
+
+    #[test]
+    fn testPersistedState() {
+        let storage = MemoryStorage::new();
+
+        let mut best = use_persisted_state_in(Rc::new(storage.clone()), "best", || 0u32);
+        assert_eq!(*best.value(), 0);
+        assert_eq!(storage.get("best"), None);
+        best.set(|n| n + 7);
+        assert_eq!(storage.get("best"), Some("v1:7".to_string()));
+
+        let best = use_persisted_state_in(Rc::new(storage.clone()), "best", || 0u32);
+        assert_eq!(*best.value(), 7);
+
+        storage.set("point", "v1:3,4");
+        let point = use_persisted_state_in(Rc::new(storage), "point", || Point(0, 0));
+        assert_eq!(*point.value(), Point(0, 0));
+    }
+
+    #[test]
+    fn testPack() {
+        let packed = pack(&["abc", "", "1:2"]);
+        assert_eq!(packed, "3:abc0:3:1:2");
+        assert_eq!(unpack(&packed), Some(vec!["abc", "", "1:2"]));
+        assert_eq!(unpack("5:abc"), None);
+
+        let nested = vec![Some(Point(1, 2)), None];
+        assert_eq!(Vec::<Option<Point>>::decode(&nested.encode()), Some(nested));
+    }
+
+    #[test]
+    fn testRng() {
+        let mut rng = ChaCha12Rng::seed_from_u64(9);
+        (0..5).for_each(|_| {
+            rng.gen::<u64>();
+        });
+        let mut restored = ChaCha12Rng::decode(&rng.encode()).unwrap();
+        assert_eq!(restored.gen::<u64>(), rng.gen::<u64>());
     }
-}
\ No newline at end of file
+}
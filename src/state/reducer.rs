@@ -0,0 +1,120 @@
+use std::{cell::Ref, fmt, rc::Rc};
+
+use crate::StateLib::{
+    memo::{use_memo, Memo},
+    ref_container::RefContainer,
+    state::{use_state, State, Subscription},
+};
+
+type Reducer<S, A> = Rc<dyn Fn(S, A) -> S>;
+type Listener<A> = Rc<dyn Fn(&A)>;
+
+// state that only changes through `dispatch`, each action run through a pure reducer.
+pub struct Store<S, A> {
+    state: State<S>,
+    reducer: Reducer<S, A>,
+    listeners: RefContainer<Vec<Listener<A>>>,
+}
+
+impl<S, A> Store<S, A> {
+    pub fn value(&self) -> Ref<'_, S> {
+        self.state.value()
+    }
+
+    pub fn dispatch(&mut self, action: A) {
+        let listeners = self.listeners.current().clone();
+        listeners.iter().for_each(|f| f(&action));
+
+        let reducer = self.reducer.clone();
+        self.state.set(|state| reducer(state, action));
+    }
+
+    // `f` sees every action before it's reduced, e.g. to log or record them.
+    pub fn on_dispatch(&self, f: impl Fn(&A) + 'static) {
+        self.listeners.0.borrow_mut().push(Rc::new(f));
+    }
+
+    // `f` gets the new state after every `dispatch`.
+    pub fn subscribe(&self, f: impl Fn(&S) + 'static) -> Subscription<S>
+    where
+        S: Clone,
+    {
+        self.state.subscribe(f)
+    }
+
+    pub fn memo<T>(&self, f: impl Fn(&S) -> T + 'static) -> Memo<T>
+    where
+        S: Clone + 'static,
+        T: PartialEq + 'static,
+    {
+        use_memo(&self.state, f)
+    }
+}
+
+impl<S, A> Clone for Store<S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            reducer: self.reducer.clone(),
+            listeners: self.listeners.clone(),
+        }
+    }
+}
+
+impl<S: fmt::Debug, A> fmt::Debug for Store<S, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Store").field("state", &self.state).finish()
+    }
+}
+
+pub fn use_reducer<S, A>(reducer: impl Fn(S, A) -> S + 'static, f: impl FnOnce() -> S) -> Store<S, A> {
+    Store {
+        state: use_state(f),
+        reducer: Rc::new(reducer),
+        listeners: RefContainer::new(vec![]),
+    }
+}
+
+// the state `actions` lead to from `state`, without a store.
+pub fn replay<S, A>(reducer: impl Fn(S, A) -> S, state: S, actions: impl IntoIterator<Item = A>) -> S {
+    actions.into_iter().fold(state, reducer)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{replay, use_reducer};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Action {
+        Add(i32),
+        Reset,
+    }
+
+    fn reducer(count: i32, action: Action) -> i32 {
+        match action {
+            Action::Add(n) => count + n,
+            Action::Reset => 0,
+        }
+    }
+
+    #[test]
+    fn testDispatch() {
+        let mut store = use_reducer(reducer, || 0);
+        let log = Rc::new(RefCell::new(vec![]));
+        {
+            let log = log.clone();
+            store.on_dispatch(move |action| log.borrow_mut().push(*action));
+        }
+
+        store.dispatch(Action::Add(2));
+        store.dispatch(Action::Add(3));
+        assert_eq!(*store.value(), 5);
+        store.dispatch(Action::Reset);
+        store.dispatch(Action::Add(1));
+        assert_eq!(*store.value(), 1);
+
+        assert_eq!(replay(reducer, 0, log.borrow().iter().copied()), 1);
+    }
+}
@@ -1,7 +1,5 @@
 use std::{cell::{RefCell, Ref, RefMut}, rc::Rc};
 
-use crate::StateLib::persist::{Persist, PersistedOrigin};
-
 
 #[derive(Debug)]
 pub struct RefContainer<T>(pub Rc<RefCell<T>>);
@@ -31,10 +29,3 @@ impl<T> Clone for RefContainer<T> {
         Self(self.0.clone())
     }
 }
-
-
-impl<T: 'static> Persist for RefContainer<T> {
-    fn ptr(&self) -> PersistedOrigin {
-        PersistedOrigin
-    }
-}
\ No newline at end of file
@@ -1,40 +1,216 @@
-use std::cell::{Ref};
+use std::{
+    cell::{Ref, RefCell},
+    error::Error,
+    fmt,
+    mem::replace,
+    rc::{Rc, Weak},
+    thread,
+};
 
 
-use crate::StateLib::persist::{Persist, PersistedOrigin};
 use crate::StateLib::ref_container::RefContainer;
 
 
 #[derive(Debug)]
 pub struct State<T> {
-    container: RefContainer<Option<T>>,
+    container: RefContainer<Slot<T>>,
+    subscribers: RefContainer<Subscribers<T>>,
+}
+
+#[derive(Debug)]
+enum Slot<T> {
+    Ready(T),
+    // the value is out with a `set` closure.
+    Updating,
+    // a `set` closure panicked and took the value with it.
+    Poisoned,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateError {
+    // read or set from inside a `set` of the same state, e.g. through a clone.
+    Updating,
+    // set while a `value()` of it is still held, e.g. from a subscriber.
+    Borrowed,
+    // an earlier `set` panicked, `reset` gives the state a value again.
+    Poisoned,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::Updating => write!(f, "the state is being set"),
+            StateError::Borrowed => write!(f, "the state is borrowed"),
+            StateError::Poisoned => write!(f, "an earlier set of the state panicked"),
+        }
+    }
+}
+
+impl Error for StateError {}
+
+// poisons the state if the `set` closure panics while this is alive.
+struct PoisonOnPanic<'a, T>(&'a RefContainer<Slot<T>>);
+
+impl<T> Drop for PoisonOnPanic<'_, T> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            *self.0 .0.borrow_mut() = Slot::Poisoned;
+        }
+    }
+}
+
+type Callback<T> = Rc<dyn Fn(&T)>;
+
+struct Subscribers<T> {
+    next_id: usize,
+    callbacks: Vec<(usize, Callback<T>)>,
+    // `T::clone`, set by the first `subscribe`.
+    snapshot: Option<fn(&T) -> T>,
+}
+
+impl<T> fmt::Debug for Subscribers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} subscribers", self.callbacks.len())
+    }
+}
+
+// returned by `State::subscribe`, the callback keeps firing until `unsubscribe`.
+pub struct Subscription<T> {
+    subscribers: Weak<RefCell<Subscribers<T>>>,
+    id: usize,
+}
+
+impl<T> Subscription<T> {
+    pub fn unsubscribe(self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            subscribers.borrow_mut().callbacks.retain(|(id, _)| *id != self.id);
+        }
+    }
 }
 
 impl<T> State<T> {
+    // panics from inside a `set` closure of the same state, which poisons it, see `try_value`.
     pub fn value(&self) -> Ref<'_, T> {
-        Ref::map(self.container.current(), |v| {
-            v.as_ref().expect("Cannot get state!!!!!")
+        self.try_value()
+            .unwrap_or_else(|error| panic!("Cannot get state: {}", error))
+    }
+
+    pub fn try_value(&self) -> Result<Ref<'_, T>, StateError> {
+        let slot = self.container.0.try_borrow().map_err(|_| StateError::Updating)?;
+        Ref::filter_map(slot, |slot| match slot {
+            Slot::Ready(value) => Some(value),
+            _ => None,
+        })
+        .map_err(|slot| match *slot {
+            Slot::Poisoned => StateError::Poisoned,
+            _ => StateError::Updating,
         })
     }
 
     pub fn set(&mut self, f: impl FnOnce(T) -> T) {
-        let val = self.container.current_mut().take();
-        let new_v = val.map(f);
-        self.container.set_current(new_v);
-    }   
+        if let Err(error) = self.try_set(f) {
+            panic!("Cannot set state: {}", error);
+        }
+    }
 
-}
+    // `f` isn't called when it returns an error. If `f` panics the state is poisoned
+    // rather than left empty, later reads get `StateError::Poisoned`.
+    pub fn try_set(&mut self, f: impl FnOnce(T) -> T) -> Result<(), StateError> {
+        let value = {
+            let mut slot = self.container.0.try_borrow_mut().map_err(|_| StateError::Borrowed)?;
+            match replace(&mut *slot, Slot::Updating) {
+                Slot::Ready(value) => value,
+                other => {
+                    let error = match other {
+                        Slot::Poisoned => StateError::Poisoned,
+                        _ => StateError::Updating,
+                    };
+                    *slot = other;
+                    return Err(error);
+                }
+            }
+        };
 
-pub fn use_state<T>(f: impl FnOnce() -> T) -> State<T> {
+        let guard = PoisonOnPanic(&self.container);
+        let value = f(value);
+        drop(guard);
 
-    State{
-        container: RefContainer::new(Some(f()))
+        self.container.set_current(Slot::Ready(value));
+        self.notify();
+        Ok(())
+    }
+
+    // give the state a new value whatever it holds, a poisoned one too.
+    pub fn reset(&mut self, value: T) -> Result<(), StateError> {
+        let mut slot = self.container.0.try_borrow_mut().map_err(|_| StateError::Borrowed)?;
+        if matches!(*slot, Slot::Updating) {
+            return Err(StateError::Updating);
+        }
+        *slot = Slot::Ready(value);
+        drop(slot);
+        self.notify();
+        Ok(())
+    }
+
+    // `f` gets the new value after every `set`, on this state or any of its clones.
+    // it gets a copy, so it can set the state again.
+    pub fn subscribe(&self, f: impl Fn(&T) + 'static) -> Subscription<T>
+    where
+        T: Clone,
+    {
+        let mut subscribers = self.subscribers.0.borrow_mut();
+        subscribers.snapshot = Some(T::clone);
+        let id = subscribers.next_id;
+        subscribers.next_id += 1;
+        subscribers.callbacks.push((id, Rc::new(f)));
+
+        Subscription {
+            subscribers: Rc::downgrade(&self.subscribers.0),
+            id,
+        }
+    }
+
+    // like `subscribe`, but `f` only fires when `selector` picks out something new.
+    pub fn select<U>(&self, selector: impl Fn(&T) -> U + 'static, f: impl Fn(&U) + 'static) -> Subscription<T>
+    where
+        T: Clone,
+        U: PartialEq + 'static,
+    {
+        let last = RefCell::new(self.try_value().ok().map(|value| selector(&value)));
+        self.subscribe(move |value| {
+            let selected = selector(value);
+            if last.borrow().as_ref() != Some(&selected) {
+                f(&selected);
+                *last.borrow_mut() = Some(selected);
+            }
+        })
+    }
+
+    fn notify(&self) {
+        // a copy of the list, so callbacks can subscribe and unsubscribe.
+        let (callbacks, snapshot) = {
+            let subscribers = self.subscribers.current();
+            let callbacks = subscribers.callbacks.iter().map(|(_, f)| f.clone()).collect::<Vec<_>>();
+            (callbacks, subscribers.snapshot)
+        };
+        let Some(snapshot) = snapshot.filter(|_| !callbacks.is_empty()) else {
+            return;
+        };
+        // and of the value, so they can set the state without it being borrowed.
+        let value = snapshot(&self.value());
+        callbacks.iter().for_each(|f| f(&value));
     }
 }
 
-impl<T: 'static> Persist for State<T> {
-    fn ptr(&self) -> PersistedOrigin {
-        PersistedOrigin
+pub fn use_state<T>(f: impl FnOnce() -> T) -> State<T> {
+
+    State{
+        container: RefContainer::new(Slot::Ready(f())),
+        subscribers: RefContainer::new(Subscribers {
+            next_id: 0,
+            callbacks: vec![],
+            snapshot: None,
+        }),
     }
 }
 
@@ -42,12 +218,18 @@ impl<T> Clone for State<T> {
     fn clone(&self) -> Self {
         Self {
             container: self.container.clone(),
+            subscribers: self.subscribers.clone(),
         }
     }
 }
 
+#[cfg(test)]
 mod test {
-    use super::{State, use_state};
+    use std::{cell::RefCell, rc::Rc};
+
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use super::{State, StateError, use_state};
 
     #[derive(Debug, PartialEq, Eq)]
     struct Person {
@@ -82,4 +264,104 @@ mod test {
 
         // println!("{:#?}", p);
     }
+
+    #[test]
+    fn testSubscribe() {
+        let mut count = use_state(|| 0);
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        let subscription = {
+            let seen = seen.clone();
+            count.subscribe(move |n| seen.borrow_mut().push(*n))
+        };
+        count.set(|n| n + 1);
+        count.clone().set(|n| n + 1);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+
+        subscription.unsubscribe();
+        count.set(|n| n + 1);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+        assert_eq!(*count.value(), 3);
+    }
+
+    #[test]
+    fn testSelect() {
+        let mut pair = use_state(|| (1, 10));
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        {
+            let seen = seen.clone();
+            let _ = pair.select(|(a, _)| *a, move |a| seen.borrow_mut().push(*a));
+        }
+        pair.set(|(a, b)| (a, b + 1));
+        pair.set(|(a, b)| (a + 1, b));
+        pair.set(|(a, b)| (a, b + 1));
+        assert_eq!(*seen.borrow(), vec![2]);
+    }
+
+    #[test]
+    fn testPanicInSet() {
+        let mut count = use_state(|| 1);
+        let result = catch_unwind(AssertUnwindSafe(|| count.set(|_| panic!("oops"))));
+        assert!(result.is_err());
+
+        assert_eq!(count.try_value().err(), Some(StateError::Poisoned));
+        assert_eq!(count.try_set(|n| n + 1), Err(StateError::Poisoned));
+        count.reset(5).unwrap();
+        count.set(|n| n + 1);
+        assert_eq!(*count.value(), 6);
+    }
+
+    #[test]
+    fn testReentrantSet() {
+        let mut count = use_state(|| 1);
+        let mut cloned = count.clone();
+
+        count.set(|n| {
+            assert_eq!(cloned.try_value().err(), Some(StateError::Updating));
+            assert_eq!(cloned.try_set(|n| n * 10), Err(StateError::Updating));
+            n + 1
+        });
+        assert_eq!(*count.value(), 2);
+
+        let value = count.value();
+        assert_eq!(cloned.try_set(|n| n + 1), Err(StateError::Borrowed));
+        drop(value);
+        cloned.set(|n| n + 1);
+        assert_eq!(*count.value(), 3);
+    }
+
+    #[test]
+    fn testValueInSet() {
+        let mut count = use_state(|| 1);
+        let cloned = count.clone();
+
+        // a plain `value()` can't wait for the `set` it's inside of, so it panics and poisons.
+        let result = catch_unwind(AssertUnwindSafe(|| count.set(|n| n + *cloned.value())));
+        assert!(result.is_err());
+        assert_eq!(count.try_value().err(), Some(StateError::Poisoned));
+        count.reset(1).unwrap();
+        assert_eq!(*count.value(), 1);
+    }
+
+    #[test]
+    fn testSetInSubscriber() {
+        let mut count = use_state(|| 0);
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        {
+            let cloned = count.clone();
+            let seen = seen.clone();
+            let _ = count.subscribe(move |n| {
+                seen.borrow_mut().push(*n);
+                // keep it even.
+                if n % 2 == 1 {
+                    cloned.clone().set(|n| n + 1);
+                }
+            });
+        }
+        count.set(|n| n + 1);
+        assert_eq!(*count.value(), 2);
+        assert_eq!(*seen.borrow(), vec![1, 2]);
+    }
 }
@@ -0,0 +1,240 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::GameLib::Game;
+use crate::TetrisLib::{
+    pos::Pos,
+    shape::Shape,
+    tetris::{Action, Tetris},
+};
+
+// how much each feature of the board counts, the defaults are the classic
+// hand-tuned values for aggregate height, lines, holes and bumpiness.
+#[derive(Debug, Clone, Copy)]
+pub struct Weights {
+    pub height: f64,
+    pub lines: f64,
+    pub holes: f64,
+    pub bumpiness: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Self {
+            height: -0.510066,
+            lines: 0.760666,
+            holes: -0.35663,
+            bumpiness: -0.184483,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Placement {
+    pub moves: Vec<Action>,
+    pub score: f64,
+}
+
+// every place the current shape can be moved to and dropped from, best first. the search tries
+// every mix of shifts, rotations and steps down, so it also finds rotations after a shift and
+// tucks under overhangs. `Tetris` has no next or hold piece, so only the current shape is searched.
+pub fn placements(tetris: &Tetris, weights: &Weights) -> Vec<Placement> {
+    let fixed: HashSet<Pos> = tetris
+        .fixed_shapes
+        .iter()
+        .flat_map(|shape| shape.positions.iter().copied())
+        .collect();
+
+    // shapes turn around their anchor, so it and the number of turns tell where a shape is.
+    let start = tetris.current_shape.clone();
+    let mut seen = HashSet::from([(start.anchor, 0)]);
+    let mut landings = HashSet::new();
+    let mut queue = VecDeque::from([(start, 0, vec![])]);
+    let mut placements = vec![];
+    // breadth first, so each landing is kept with the fewest moves that reach it.
+    while let Some((shape, turns, moves)) = queue.pop_front() {
+        if !tetris.could_move(&(&shape + Pos(0, 1))) && landings.insert(cells(&shape)) {
+            // the steps straight down at the end are left to the drop.
+            let mut moves = moves.clone();
+            while moves.last() == Some(&Action::Down) {
+                moves.pop();
+            }
+            moves.push(Action::Drop);
+            placements.push(Placement {
+                moves,
+                score: evaluate(tetris, &fixed, &shape, weights),
+            });
+        }
+
+        for (action, next, turns) in [
+            (Action::Left, &shape + Pos(-1, 0), turns),
+            (Action::Right, &shape + Pos(1, 0), turns),
+            (Action::Rotate, shape.rotate(), (turns + 1) % 4),
+            (Action::Down, &shape + Pos(0, 1), turns),
+        ] {
+            if seen.insert((next.anchor, turns)) && tetris.could_move(&next) {
+                let mut moves = moves.clone();
+                moves.push(action);
+                queue.push_back((next, turns, moves));
+            }
+        }
+    }
+
+    placements.sort_by(|a, b| b.score.total_cmp(&a.score));
+    placements
+}
+
+pub fn best_placement(tetris: &Tetris, weights: &Weights) -> Option<Placement> {
+    placements(tetris, weights).into_iter().next()
+}
+
+// place the current shape where the bot likes it best.
+pub fn play(tetris: &mut Tetris, weights: &Weights) {
+    if tetris.failed {
+        return;
+    }
+    if let Some(placement) = best_placement(tetris, weights) {
+        placement.moves.into_iter().for_each(|action| tetris.apply(action));
+    }
+}
+
+// average lines cleared over `games` games, each stopped after `max_pieces` pieces at the latest.
+pub fn benchmark(games: u64, max_pieces: usize, weights: &Weights) -> f64 {
+    let total: u32 = (0..games)
+        .map(|seed| {
+            let mut tetris = Tetris::default().with_seed(seed);
+            for _ in 0..max_pieces {
+                if tetris.failed {
+                    break;
+                }
+                play(&mut tetris, weights);
+            }
+            tetris.lines
+        })
+        .sum();
+    total as f64 / games as f64
+}
+
+// the cells of `shape`, in an order that can be compared and hashed.
+fn cells(shape: &Shape) -> Vec<(i8, i8)> {
+    let mut cells: Vec<(i8, i8)> = shape.positions.iter().map(|pos| (pos.0, pos.1)).collect();
+    cells.sort();
+    cells
+}
+
+fn evaluate(tetris: &Tetris, fixed: &HashSet<Pos>, landed: &Shape, weights: &Weights) -> f64 {
+    let (width, height) = (tetris.width as i8, tetris.height as i8);
+    let mut cells: HashSet<Pos> = fixed.union(&landed.positions).copied().collect();
+
+    let full_rows: Vec<i8> = (0..height)
+        .filter(|y| (0..width).all(|x| cells.contains(&Pos(x, *y))))
+        .collect();
+    for y in &full_rows {
+        cells = cells
+            .iter()
+            .filter(|pos| pos.1 != *y)
+            .map(|pos| if pos.1 < *y { Pos(pos.0, pos.1 + 1) } else { *pos })
+            .collect();
+    }
+
+    let heights: Vec<i32> = (0..width)
+        .map(|x| {
+            (0..height)
+                .find(|y| cells.contains(&Pos(x, *y)))
+                .map_or(0, |y| (height - y) as i32)
+        })
+        .collect();
+    let holes: i32 = (0..width)
+        .map(|x| {
+            let top = height as i32 - heights[x as usize];
+            (top..height as i32).filter(|y| !cells.contains(&Pos(x, *y as i8))).count() as i32
+        })
+        .sum();
+    let bumpiness: i32 = heights.windows(2).map(|w| (w[0] - w[1]).abs()).sum();
+    let aggregate_height: i32 = heights.iter().sum();
+
+    let mut score = weights.height * aggregate_height as f64
+        + weights.lines * full_rows.len() as f64
+        + weights.holes * holes as f64
+        + weights.bumpiness * bumpiness as f64;
+
+    // the next shape spawns in the top rows.
+    if heights.iter().any(|h| *h as i8 > height - 2) {
+        score -= 1000.0;
+    }
+    score
+}
+
+#[cfg(test)]
+mod test {
+    use super::{benchmark, best_placement, placements, Weights};
+    use crate::GameLib::{color::Color, Game};
+    use crate::TetrisLib::{pos::Pos, shape::Shape, tetris::{Action, Tetris}};
+
+    fn floor_with_gap(tetris: &mut Tetris, gap: i8) {
+        let y = tetris.height as i8 - 1;
+        tetris.fixed_shapes.push(Shape::new(
+            (0..tetris.width as i8).filter(|x| *x != gap).map(|x| Pos(x, y)).collect(),
+            Pos(0, y),
+            Color::Black,
+        ));
+    }
+
+    #[test]
+    fn testPlacementsEndWithDrop() {
+        let tetris = Tetris::default();
+        let all = placements(&tetris, &Weights::default());
+
+        assert!(!all.is_empty());
+        assert!(all.iter().all(|p| p.moves.last() == Some(&Action::Drop)));
+        assert!(all.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn testFillsTheGap() {
+        let mut tetris = Tetris::default();
+        floor_with_gap(&mut tetris, 9);
+        // an upright I shape in the top left corner.
+        tetris.current_shape = &Shape::new_i().rotate() + Pos(0, 1);
+
+        let placement = best_placement(&tetris, &Weights::default()).unwrap();
+        placement.moves.into_iter().for_each(|action| tetris.apply(action));
+
+        assert_eq!(tetris.lines, 1);
+    }
+
+    #[test]
+    fn testTucksUnderOverhang() {
+        let mut tetris = Tetris::default();
+        let y = tetris.height as i8 - 1;
+        // the bottom row is open on the left, under a block that stops anything dropping straight in.
+        tetris.fixed_shapes.push(Shape::new(
+            (5..tetris.width as i8).map(|x| Pos(x, y)).chain([Pos(0, y - 1)]).collect(),
+            Pos(0, y),
+            Color::Black,
+        ));
+        tetris.current_shape = Shape::new_i();
+
+        let tucked = placements(&tetris, &Weights::default()).into_iter().any(|placement| {
+            let mut tetris = tetris.clone();
+            placement.moves.into_iter().for_each(|action| tetris.apply(action));
+            tetris.fixed_shapes.iter().any(|shape| shape.positions.contains(&Pos(0, y)))
+        });
+        assert!(tucked);
+    }
+
+    #[test]
+    fn testBenchmark() {
+        // 100 pieces fill 40 lines at most.
+        let default = benchmark(2, 100, &Weights::default());
+        assert!(default > 20.0);
+
+        // stacks high and leaves holes.
+        let bad = Weights {
+            height: 0.5,
+            lines: 0.0,
+            holes: 0.5,
+            bumpiness: 0.5,
+        };
+        assert!(default > benchmark(2, 100, &bad));
+    }
+}
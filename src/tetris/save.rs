@@ -0,0 +1,135 @@
+// saving a game in progress, see `StateLib::persist`.
+use super::tetris::Tetris;
+use crate::StateLib::persist::Persist;
+
+impl Persist for Tetris {
+    fn encode(&self) -> String {
+        codec::encode(self)
+    }
+
+    fn decode(text: &str) -> Option<Self> {
+        codec::decode(text).filter(is_playable)
+    }
+}
+
+// sizes a new game couldn't have, e.g. edited by hand.
+fn is_playable(tetris: &Tetris) -> bool {
+    (Tetris::MIN_WIDTH..=Tetris::MAX_SIZE).contains(&tetris.width)
+        && (Tetris::MIN_HEIGHT..=Tetris::MAX_SIZE).contains(&tetris.height)
+}
+
+// with serde the game is saved through its derives, so there's only ever one format.
+#[cfg(feature = "serde")]
+mod codec {
+    pub use crate::StateLib::persist::{decode_json as decode, encode_json as encode};
+}
+
+// written out by hand without it.
+#[cfg(not(feature = "serde"))]
+mod codec {
+    use std::collections::HashSet;
+
+    use crate::{
+        StateLib::persist::{pack, unpack, Persist},
+        TetrisLib::{pos::Pos, shape::Shape, tetris::Tetris},
+    };
+
+    impl Persist for Pos {
+        fn encode(&self) -> String {
+            format!("{},{}", self.0, self.1)
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            let (x, y) = text.split_once(',')?;
+            Some(Pos(x.parse().ok()?, y.parse().ok()?))
+        }
+    }
+
+    impl Persist for Shape {
+        fn encode(&self) -> String {
+            let positions = self.positions.iter().copied().collect::<Vec<Pos>>();
+            pack(&[self.typ.encode(), self.anchor.encode(), positions.encode()])
+        }
+
+        fn decode(text: &str) -> Option<Self> {
+            let [typ, anchor, positions] = unpack(text)?[..] else {
+                return None;
+            };
+            Some(Shape::new(
+                Vec::<Pos>::decode(positions)?.into_iter().collect::<HashSet<Pos>>(),
+                Pos::decode(anchor)?,
+                Persist::decode(typ)?,
+            ))
+        }
+    }
+
+    pub fn encode(tetris: &Tetris) -> String {
+        pack(&[
+            tetris.width.encode(),
+            tetris.height.encode(),
+            tetris.current_shape.encode(),
+            tetris.fixed_shapes.encode(),
+            tetris.failed.encode(),
+            tetris.lines.encode(),
+            tetris.rng.encode(),
+        ])
+    }
+
+    pub fn decode(text: &str) -> Option<Tetris> {
+        let [width, height, current_shape, fixed_shapes, failed, lines, rng] = unpack(text)?[..] else {
+            return None;
+        };
+        Some(Tetris {
+            current_shape: Shape::decode(current_shape)?,
+            fixed_shapes: Persist::decode(fixed_shapes)?,
+            width: Persist::decode(width)?,
+            height: Persist::decode(height)?,
+            failed: Persist::decode(failed)?,
+            lines: Persist::decode(lines)?,
+            rng: Persist::decode(rng)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        GameLib::Game,
+        StateLib::persist::{deserialize, serialize},
+        TetrisLib::{
+            bot::{self, Weights},
+            tetris::Tetris,
+        },
+    };
+
+    #[test]
+    fn testSaveAndResume() {
+        let mut tetris = Tetris::new(10, 25).with_seed(4);
+        (0..30).for_each(|_| bot::play(&mut tetris, &Weights::default()));
+
+        let mut resumed: Tetris = deserialize(&serialize(&tetris)).unwrap();
+        assert_eq!(resumed.cells(), tetris.cells());
+        assert_eq!(resumed.lines, tetris.lines);
+
+        // the same shapes keep coming.
+        (0..30).for_each(|_| {
+            bot::play(&mut tetris, &Weights::default());
+            bot::play(&mut resumed, &Weights::default());
+        });
+        assert_eq!(resumed.cells(), tetris.cells());
+    }
+
+    #[test]
+    fn testRejectBoardSize() {
+        let resumes = |width, height| {
+            let mut tetris = Tetris::new(10, 25);
+            (tetris.width, tetris.height) = (width, height);
+            deserialize::<Tetris>(&serialize(&tetris)).is_some()
+        };
+        assert!(resumes(Tetris::MIN_WIDTH, Tetris::MAX_SIZE));
+        assert!(!resumes(Tetris::MIN_WIDTH - 1, 25));
+        assert!(!resumes(10, Tetris::MIN_HEIGHT - 1));
+        assert!(!resumes(200, 25));
+        assert!(!resumes(10, 200));
+    }
+}
@@ -1,24 +1,25 @@
-use rand::random;
-use crate::TetrisLib::pos::Pos;
+use rand::Rng;
+use crate::{GameLib::color::Color, TetrisLib::pos::Pos};
 use std::{collections::HashSet, ops::Add};
 
 
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shape {
     pub positions: HashSet<Pos>,
     pub anchor: Pos,
-    pub typ: &'static str,
+    pub typ: Color,
 }
 
 
 macro_rules! shape_factory {
-    ( $( $fn:ident - $typ:literal - $anchor:expr => $( $positions:expr),* ;)* )=> {
+    ( $( $fn:ident - $typ:ident - $anchor:expr => $( $positions:expr),* ;)* )=> {
 
         $(
             pub fn $fn() -> Self {
                 Self {
-                    typ: $typ,
+                    typ: Color::$typ,
                     positions: ( $($positions),* ).into_iter().collect::<HashSet<Pos>>(),
                     anchor: $anchor,
                 }
@@ -48,7 +49,7 @@ impl Add<Pos> for &Shape {
 
 
 impl Shape {
-    pub fn new(positions: HashSet<Pos>, anchor: Pos, typ: &'static str) -> Self {
+    pub fn new(positions: HashSet<Pos>, anchor: Pos, typ: Color) -> Self {
         Self {
             positions,
             anchor,
@@ -57,17 +58,17 @@ impl Shape {
     }
 
     shape_factory! {
-        new_i - "🟦" - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(2,0), Pos(3,0), ];
-        new_o - "🟨" - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(0,1), Pos(1,1), ];
-        new_t - "🟪" - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(2,0), Pos(1,1), ];
-        new_j - "⬛" - Pos(1, 1) => [Pos(0,2), Pos(1,0), Pos(1,1), Pos(1,2), ];
-        new_l - "🟧" - Pos(0, 1) => [Pos(0,0), Pos(0,1), Pos(0,2), Pos(1,2), ];
-        new_s - "🟩" - Pos(1, 0) => [Pos(1,0), Pos(2,0), Pos(0,1), Pos(1,1), ];
-        new_z - "🟥" - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(1,1), Pos(2,1), ];
+        new_i - Blue - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(2,0), Pos(3,0), ];
+        new_o - Yellow - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(0,1), Pos(1,1), ];
+        new_t - Purple - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(2,0), Pos(1,1), ];
+        new_j - Black - Pos(1, 1) => [Pos(0,2), Pos(1,0), Pos(1,1), Pos(1,2), ];
+        new_l - Orange - Pos(0, 1) => [Pos(0,0), Pos(0,1), Pos(0,2), Pos(1,2), ];
+        new_s - Green - Pos(1, 0) => [Pos(1,0), Pos(2,0), Pos(0,1), Pos(1,1), ];
+        new_z - Red - Pos(1, 0) => [Pos(0,0), Pos(1,0), Pos(1,1), Pos(2,1), ];
     }
 
-    pub fn random_shape() -> Self {
-        match (random::<f64>() * 7.0).floor() as u8 {
+    pub fn random_shape(rng: &mut impl Rng) -> Self {
+        match (rng.gen::<f64>() * 7.0).floor() as u8 {
             0 => Self::new_i(),
             1 => Self::new_o(),
             2 => Self::new_t(),
@@ -110,6 +111,7 @@ impl Shape {
 
 }
 
+#[cfg(test)]
 mod tests {
     use crate::TetrisLib::shape::Shape;
 
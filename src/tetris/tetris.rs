@@ -1,6 +1,8 @@
 
+use crate::GameLib::{Game, Status};
 use crate::TetrisLib::{pos::Pos, shape::Shape};
-use crate::StateLib::persist::{PersistedOrigin, Persist};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
 
 use std::mem::replace;
 
@@ -9,49 +11,138 @@ pub enum Direction {
     Right,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Left,
+    Right,
+    Rotate,
+    // one row down.
+    Down,
+    // let the shape fall until it lands.
+    Drop,
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tetris {
     pub current_shape: Shape,
     pub fixed_shapes: Vec<Shape>,
     pub width: u8,
     pub height: u8,
     pub failed: bool,
+    // number of full lines removed so far.
+    pub lines: u32,
+    // decides the next shape.
+    pub rng: ChaCha12Rng,
 }
 
-impl Persist for Tetris {
-    fn ptr(&self) -> crate::StateLib::persist::PersistedOrigin {
-        PersistedOrigin
-    }
-}
 
 
 impl Default for Tetris {
     fn default() -> Self {
-        Self {
-            current_shape: &Shape::random_shape() + Pos((10 / 2 - 1) as i8, 0),
-            fixed_shapes: vec![],
-            width: 10,
-            height: 25,
-            failed: false,
-        }
+        Self::new(10, 25)
     }
 }
 
 
 
 
+impl Game for Tetris {
+    type Action = Action;
+
+    fn width(&self) -> u32 {
+        self.width as u32
+    }
+
+    fn height(&self) -> u32 {
+        self.height as u32
+    }
+
+    fn cell(&self, x: u32, y: u32) -> &str {
+        self.getPosition(Pos(x as i8, y as i8))
+    }
+
+    // paints the shapes once instead of searching them for every cell.
+    fn cells(&self) -> Vec<&str> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut cells = vec![""; width * height];
+        self.fixed_shapes
+            .iter()
+            .chain(std::iter::once(&self.current_shape))
+            .for_each(|shape| {
+                shape
+                    .positions
+                    .iter()
+                    .filter(|Pos(x, y)| *x >= 0 && *y >= 0 && (*x as usize) < width && (*y as usize) < height)
+                    .for_each(|Pos(x, y)| cells[*y as usize * width + *x as usize] = shape.typ.square())
+            });
+        cells
+    }
+
+    fn apply(&mut self, action: Action) {
+        match action {
+            Action::Left => {
+                self.shift(Direction::Left);
+            }
+            Action::Right => {
+                self.shift(Direction::Right);
+            }
+            Action::Rotate => {
+                self.rotate();
+            }
+            Action::Down => {
+                Tetris::tick(self);
+            }
+            Action::Drop => self.drop_down(),
+        }
+    }
+
+    fn tick(&mut self) {
+        Tetris::tick(self);
+    }
+
+    fn status(&self) -> Status {
+        if self.failed {
+            Status::Lost(format!("Game over, {} lines", self.lines))
+        } else {
+            Status::Running
+        }
+    }
+
+    fn score(&self) -> String {
+        format!("lines: {}", self.lines)
+    }
+}
+
 impl Tetris {
+    // the I piece spawns across `width / 2 - 1 ..= width / 2 + 2`.
+    pub const MIN_WIDTH: u8 = 5;
+    // shapes are up to 3 cells tall.
+    pub const MIN_HEIGHT: u8 = 4;
+    // cells are addressed with `i8`.
+    pub const MAX_SIZE: u8 = i8::MAX as u8;
+
     pub fn new(width: u8, height: u8) -> Self {
+        let mut rng = ChaCha12Rng::from_entropy();
         Self {
-            current_shape: &Shape::random_shape() + Pos((width / 2 - 1) as i8, 0),
+            current_shape: &Shape::random_shape(&mut rng) + Pos((width / 2 - 1) as i8, 0),
             fixed_shapes: vec![],
             failed: false,
+            lines: 0,
             width,
 
             height,
+            rng,
         }
     }
 
+    // the same shapes in the same order every time.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
+        self.current_shape = &Shape::random_shape(&mut self.rng) + Pos((self.width / 2 - 1) as i8, 0);
+        self
+    }
+
     // move the current shape down by one row.
     pub fn tick(&mut self) -> Option<Shape> {
         let new_shape = &self.current_shape + Pos(0, 1);
@@ -60,10 +151,8 @@ impl Tetris {
         if !self.could_move(&new_shape) {
             // need to change current shape.
             // and put the old shape into fixed_shapes
-            let fixed_shape = replace(
-                &mut self.current_shape,
-                &Shape::random_shape() + Pos((self.width / 2 - 1) as i8, 0),
-            );
+            let next_shape = &Shape::random_shape(&mut self.rng) + Pos((self.width / 2 - 1) as i8, 0);
+            let fixed_shape = replace(&mut self.current_shape, next_shape);
             self.fixed_shapes.push(fixed_shape);
 
             self.remove_full_lines();
@@ -80,6 +169,11 @@ impl Tetris {
         }
     }
 
+    // let the current shape fall until it lands.
+    pub fn drop_down(&mut self) {
+        while !self.failed && self.tick().is_some() {}
+    }
+
     pub fn is_out_of_bounds(&self, shape: &Shape) -> bool {
         !shape.positions.iter().all(|pos| {
             pos.0 >= 0 && pos.0 < self.width as i8 && pos.1 >= 0 && pos.1 < self.height as i8
@@ -133,6 +227,7 @@ impl Tetris {
     pub fn remove_full_lines(&mut self) {
         for y in 0..self.height {
             if self.is_line_full(y as i8) {
+                self.lines += 1;
                 self.fixed_shapes
                     .iter_mut()
                     .for_each(|shape: &mut Shape| shape.remove(y as i8))
@@ -147,7 +242,7 @@ impl Tetris {
             .iter()
             .find(|p| pos.0 == p.0 && pos.1 == p.1);
         if res.is_some() {
-            return self.current_shape.typ;
+            return self.current_shape.typ.square();
         }
 
         let res = self
@@ -155,7 +250,7 @@ impl Tetris {
             .iter()
             .find(|shape| shape.positions.iter().any(|p| p.0 == pos.0 && p.1 == pos.1));
         if let Some(shape) = res {
-            return shape.typ;
+            return shape.typ.square();
         }
 
         ""
@@ -170,8 +265,9 @@ impl Tetris {
 
 }
 
+#[cfg(test)]
 mod test {
-    use crate::TetrisLib::tetris::Tetris;
+    use crate::{GameLib::Game, TetrisLib::tetris::Tetris};
 
     #[test]
     fn testNewTetris() {
@@ -183,6 +279,25 @@ mod test {
         println!("{:#?}", Tetris::default());
     }
 
+    #[test]
+    fn testSmallestBoard() {
+        (0..20).for_each(|seed| {
+            let mut tetris = Tetris::new(Tetris::MIN_WIDTH, Tetris::MIN_HEIGHT).with_seed(seed);
+            assert!(!tetris.is_out_of_bounds(&tetris.current_shape));
+            tetris.drop_down();
+            assert!(!tetris.is_out_of_bounds(&tetris.current_shape));
+        });
+    }
+
+    #[test]
+    fn testLargestBoard() {
+        let mut tetris = Tetris::new(Tetris::MAX_SIZE, Tetris::MAX_SIZE).with_seed(0);
+        tetris.drop_down();
+
+        assert!(!tetris.failed);
+        assert!(tetris.fixed_shapes[0].positions.iter().any(|pos| pos.1 == tetris.height as i8 - 1));
+    }
+
     #[test]
     fn testTick() {
         let mut tetris = Tetris::default();
@@ -194,4 +309,26 @@ mod test {
         assert_eq!(tetris.fixed_shapes.len(), 1);
         // println!("{:#?}", tetris);
     }
+
+    #[test]
+    fn testDropDown() {
+        let mut tetris = Tetris::default();
+        tetris.drop_down();
+
+        assert_eq!(tetris.fixed_shapes.len(), 1);
+        assert!(tetris.fixed_shapes[0].positions.iter().any(|pos| pos.1 == tetris.height as i8 - 1));
+    }
+
+    #[test]
+    fn testCells() {
+        let mut tetris = Tetris::default();
+        (0..5).for_each(|_| tetris.drop_down());
+        tetris.tick();
+
+        let expected = tetris
+            .iter_positions()
+            .map(|pos| tetris.getPosition(pos))
+            .collect::<Vec<&str>>();
+        assert_eq!(tetris.cells(), expected);
+    }
 }
@@ -0,0 +1,94 @@
+use super::{
+    bot,
+    tetris::{Action, Tetris},
+};
+use crate::DomLib::{
+    domtools::DomElements,
+    grid::{default_save_key, run_grid, Autoplay, GridOptions, Mount, Renderer, SaveKey},
+};
+#[cfg(feature = "serde")]
+use crate::StateLib::persist::encode_json;
+use wasm_bindgen::prelude::*;
+use web_sys::Element;
+
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Default)]
+pub struct TetrisOptions {
+    pub width: Option<u8>,
+    pub height: Option<u8>,
+    // adds a `theme-<name>` class to the container.
+    pub theme: Option<String>,
+    pub renderer: Renderer,
+    // where an unfinished game is saved and resumed from,
+    // `"tetris-<target id>"` by default, or `"tetris"` for a target without an id.
+    pub save_key: Option<String>,
+    // ignore a saved game and start a new one in its place.
+    pub new_game: bool,
+}
+
+#[wasm_bindgen]
+impl TetrisOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn keymap(code: &str) -> Option<Action> {
+    match code {
+        "ArrowLeft" => Some(Action::Left),
+        "ArrowRight" => Some(Action::Right),
+        "ArrowUp" => Some(Action::Rotate),
+        "ArrowDown" => Some(Action::Down),
+        _ => None,
+    }
+}
+
+fn play_bot(tetris: &mut Tetris) {
+    bot::play(tetris, &bot::Weights::default());
+}
+
+pub fn run_tetris(domElements: &DomElements, target: &Element, options: &TetrisOptions) -> Mount {
+    let save_key = SaveKey::claim(
+        &options
+            .save_key
+            .clone()
+            .unwrap_or_else(|| default_save_key("tetris", target)),
+    );
+    // the options only shape a new game, a saved one resumes as it was.
+    let saved = save_key.as_ref().filter(|_| !options.new_game).and_then(SaveKey::load);
+    let tetris = saved.unwrap_or_else(|| {
+        Tetris::new(
+            options.width.unwrap_or(10).clamp(Tetris::MIN_WIDTH, Tetris::MAX_SIZE),
+            options.height.unwrap_or(25).clamp(Tetris::MIN_HEIGHT, Tetris::MAX_SIZE),
+        )
+    });
+    let mut class_name = "tetris-container".to_string();
+    if let Some(theme) = &options.theme {
+        class_name.push_str(&format!(" theme-{}", theme));
+    }
+
+    run_grid(
+        domElements,
+        target,
+        tetris,
+        GridOptions {
+            class_name,
+            renderer: options.renderer,
+            save_key,
+            #[cfg(feature = "serde")]
+            to_json: Some(encode_json),
+            #[cfg(not(feature = "serde"))]
+            to_json: None,
+            // gravity.
+            step_ms: 1000.0,
+            keymap,
+            // press `B` to watch the bot play.
+            autoplay: Some(Autoplay {
+                key: "KeyB",
+                play: play_bot,
+            }),
+        },
+    )
+}